@@ -5,15 +5,73 @@ use crate::color::Color;
 use crate::hittables::hittable::Hittable;
 use crate::hittables::hittable_list::HittableList;
 use crate::math::interval::Interval;
-use crate::math::ray::Ray;
+use crate::math::ray::{Ray, RayDifferentials};
 use crate::math::vec3::{Point3, Vec3};
 use crate::multithreaded_renderer;
 use crate::multithreaded_renderer::Cord;
+use crate::texture::environment::EnvironmentMap;
+use crate::texture::sky::PhysicalSky;
 use crate::{hittables::hittable::HitRecord, util};
 use crossbeam_channel::unbounded;
 use rand::prelude::SliceRandom;
 use threadpool::ThreadPool;
 
+// What a ray that leaves the scene sees.
+#[derive(Clone)]
+pub enum Background {
+    None,                             // Black, so only emissive materials light the scene
+    Constant(Color),                  // The same color in every direction
+    Gradient(Color, Color),           // A blend from the color straight down to the one straight up
+    Environment(Arc<EnvironmentMap>), // Light from a latitude-longitude image
+    PhysicalSky(Arc<PhysicalSky>),    // A daylight sky and sun
+}
+
+impl Background {
+    pub fn sky() -> Background {
+        // The white to blue sky the scenes have always used.
+        Background::Gradient(Color::new(1.0, 1.0, 1.0), Color::new(0.5, 0.7, 1.0))
+    }
+
+    fn value(&self, r: &Ray) -> Color {
+        match self {
+            Background::None => Color::blank(),
+            Background::Constant(color) => *color,
+            Background::Gradient(bottom, top) => {
+                let unit_direction = Vec3::unit_vector(r.direction());
+                let a = 0.5 * (unit_direction.y() + 1.0);
+                (1.0 - a) * *bottom + a * *top
+            }
+            Background::Environment(map) => map.value(&r.direction()),
+            Background::PhysicalSky(sky) => sky.value(&r.direction()),
+        }
+    }
+
+    fn is_sampled(&self) -> bool {
+        // Whether the background has bright spots worth aiming rays at.
+        matches!(
+            self,
+            Background::Environment(_) | Background::PhysicalSky(_)
+        )
+    }
+
+    fn sample_direction(&self) -> Vec3 {
+        match self {
+            Background::Environment(map) => map.sample_direction(),
+            Background::PhysicalSky(sky) => sky.sample_direction(),
+            _ => Vec3::random_unit_vector(),
+        }
+    }
+
+    fn pdf(&self, direction: &Vec3) -> f64 {
+        // The solid angle density of sample_direction().
+        match self {
+            Background::Environment(map) => map.pdf(direction),
+            Background::PhysicalSky(sky) => sky.pdf(direction),
+            _ => 1.0 / (4.0 * util::PI),
+        }
+    }
+}
+
 pub struct CameraSettings {
     pub num_threads: usize,
     pub aspect_ratio: f64,
@@ -26,9 +84,10 @@ pub struct CameraSettings {
     pub vup: Vec3,
     pub defocus_angle: f64,
     pub focus_dist: f64,
+    pub background: Background,
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone)]
 pub struct Camera {
     center: Point3,      // Camera center
     pixel00_loc: Point3, // Location of pixel 0, 0
@@ -46,19 +105,22 @@ pub struct Camera {
     image_height: usize,
     samples_per_pixel: i64,
     max_depth: i64,
+    background: Background,
 }
 
 impl Camera {
     pub fn render(self, world: Arc<HittableList>) {
+        let (image_width, image_height) = (self.image_width, self.image_height);
+        let cam = Arc::new(self);
         let (window_s, window_r) = unbounded();
         let (worker_s, worker_r) = unbounded();
 
-        let pool = ThreadPool::new(self.num_threads + 1);
+        let pool = ThreadPool::new(cam.num_threads + 1);
 
         pool.execute(move || {
             let mut all_px = vec![];
-            for j in 0..self.image_height {
-                for i in 0..self.image_width {
+            for j in 0..image_height {
+                for i in 0..image_width {
                     all_px.push((i, j));
                 }
             }
@@ -71,25 +133,26 @@ impl Camera {
             }
         });
 
-        for _ in 0..self.num_threads {
+        for _ in 0..cam.num_threads {
+            let cam = cam.clone();
             let wr = worker_r.clone();
             let ws = window_s.clone();
             let w = world.clone();
             pool.execute(move || {
                 while let Ok(cord) = wr.recv() {
                     let mut pixel_color = Color::blank();
-                    for _ in 0..self.samples_per_pixel {
-                        let r = self.get_ray(cord.x as f64, cord.y as f64);
-                        pixel_color += self.ray_color(&r, self.max_depth, &w);
+                    for _ in 0..cam.samples_per_pixel {
+                        let r = cam.get_ray(cord.x as f64, cord.y as f64);
+                        pixel_color += cam.ray_color(&r, cam.max_depth, &w);
                     }
-                    color::write_color(&ws, &cord, &(self.pixel_samples_scale * pixel_color));
+                    color::write_color(&ws, &cord, &(cam.pixel_samples_scale * pixel_color));
                 }
             });
         }
 
         // The `draw` function contains the window and must run on the main thread.
         // This call will block until the user closes the window.
-        multithreaded_renderer::draw(self.image_height, self.image_width, window_r);
+        multithreaded_renderer::draw(image_height, image_width, window_r);
     }
 
     pub fn initialize(camera_settings: CameraSettings) -> Camera {
@@ -111,6 +174,7 @@ impl Camera {
             defocus_disk_u: Point3::blank(),
             defocus_disk_v: Point3::blank(),
             defocus_angle: camera_settings.defocus_angle,
+            background: camera_settings.background,
         };
 
         // Determine viewport dimensions.
@@ -146,7 +210,7 @@ impl Camera {
         cam
     }
 
-    fn ray_color(self, r: &Ray, depth: i64, world: &HittableList) -> Color {
+    fn ray_color(&self, r: &Ray, depth: i64, world: &HittableList) -> Color {
         // If we've exceeded the ray bounce limit, no more light is gathered.
         if depth <= 0 {
             return Color::blank();
@@ -155,20 +219,76 @@ impl Camera {
         let mut rec = HitRecord::blank();
 
         if world.hit(r, Interval::new(0.001, util::INFINITY), &mut rec) {
-            let mut scattered = Ray::blank();
-            let mut attenuation = Color::blank();
-            if rec.mat.scatter(r, &rec, &mut attenuation, &mut scattered) {
-                return attenuation * self.ray_color(&scattered, depth - 1, world);
+            return self.hit_color(r, &mut rec, depth, world);
+        }
+
+        return self.background.value(r);
+    }
+
+    fn hit_color(&self, r: &Ray, rec: &mut HitRecord, depth: i64, world: &HittableList) -> Color {
+        // The light leaving a hit back along r.
+        rec.compute_differentials(r);
+        let mut scattered = Ray::blank();
+        let mut attenuation = Color::blank();
+        let color_from_emission = rec.mat.emitted(rec.u, rec.v, &rec.p);
+
+        // Materials with a density send half their rays towards the bright parts of the
+        // background instead. Those rays only gather the background light that gets through
+        // the scene, found by tracking transmittance through volumes, and weight it by the
+        // density of the two strategies combined. The material's own rays weight background
+        // light they reach the same way, and everything else by their density alone. The
+        // strategy is picked first, so a material sample that fails still leaves the
+        // background's half. Mirror-like materials keep their own ray.
+        if self.background.is_sampled() && rec.mat.has_density() {
+            if depth <= 1 {
+                return color_from_emission;
             }
-            return Color::blank();
+
+            if util::random_double() < 0.5 {
+                let direction = self.background.sample_direction();
+                scattered = Ray::new_timed(rec.p, direction, r.time());
+                let pdf = 0.5 * rec.mat.scattering_pdf(r, rec, &scattered)
+                    + 0.5 * self.background.pdf(&direction);
+                if pdf <= 0.0 {
+                    return color_from_emission;
+                }
+                let f = rec.mat.eval(r, rec, &scattered);
+                if f.near_zero() {
+                    return color_from_emission;
+                }
+                let transmittance =
+                    world.transmittance(&scattered, Interval::new(0.001, util::INFINITY));
+                return color_from_emission
+                    + f * transmittance * self.background.value(&scattered) / pdf;
+            }
+
+            if !rec.mat.scatter(r, rec, &mut attenuation, &mut scattered) {
+                return color_from_emission;
+            }
+            let scattering_pdf = rec.mat.scattering_pdf(r, rec, &scattered);
+            if scattering_pdf <= 0.0 {
+                return color_from_emission;
+            }
+            let mut next = HitRecord::blank();
+            if !world.hit(&scattered, Interval::new(0.001, util::INFINITY), &mut next) {
+                let pdf = 0.5 * scattering_pdf + 0.5 * self.background.pdf(&scattered.direction());
+                return color_from_emission
+                    + rec.mat.eval(r, rec, &scattered) * self.background.value(&scattered) / pdf;
+            }
+            return color_from_emission
+                + rec.mat.eval(r, rec, &scattered)
+                    * self.hit_color(&scattered, &mut next, depth - 1, world)
+                    / (0.5 * scattering_pdf);
         }
 
-        let unit_direction = Vec3::unit_vector(r.direction());
-        let a = 0.5 * (unit_direction.y() + 1.0);
-        return (1.0 - a) * Color::new(1.0, 1.0, 1.0) + a * Color::new(0.5, 0.7, 1.0);
+        if rec.mat.scatter(r, rec, &mut attenuation, &mut scattered) {
+            return color_from_emission
+                + attenuation * self.ray_color(&scattered, depth - 1, world);
+        }
+        return color_from_emission;
     }
 
-    fn get_ray(self, i: f64, j: f64) -> Ray {
+    fn get_ray(&self, i: f64, j: f64) -> Ray {
         // Construct a camera ray originating from the origin and directed at randomly sampled
         // point around the pixel location i, j.
         let offset = self.sample_square();
@@ -184,10 +304,20 @@ impl Camera {
         let ray_direction = pixel_sample - ray_origin;
         let ray_time = util::random_double();
 
-        return Ray::new_timed(ray_origin, ray_direction, ray_time);
+        // Offset rays towards the next pixel over and down. With many samples per pixel each
+        // one only needs to cover a share of the pixel, so the offsets shrink to match.
+        let scale = (1.0 / (self.samples_per_pixel as f64).sqrt()).max(0.125);
+        let mut ray = Ray::new_timed(ray_origin, ray_direction, ray_time);
+        ray.differentials = Some(RayDifferentials {
+            rx_origin: ray_origin,
+            rx_direction: ray_direction + scale * self.pixel_delta_u,
+            ry_origin: ray_origin,
+            ry_direction: ray_direction + scale * self.pixel_delta_v,
+        });
+        return ray;
     }
 
-    fn sample_square(self) -> Vec3 {
+    fn sample_square(&self) -> Vec3 {
         // Returns the vector to a random point in the [-.5,-.5]-[+.5,+.5] unit square.
         return Vec3::new(
             util::random_double() - 0.5,
@@ -196,9 +326,123 @@ impl Camera {
         );
     }
 
-    fn defocus_disk_sample(self) -> Point3 {
+    fn defocus_disk_sample(&self) -> Point3 {
         // Returns a random point in the camera defocus disk.
         let p = Vec3::random_in_unit_disk();
         return self.center + (p[0] * self.defocus_disk_u) + (p[1] * self.defocus_disk_v);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hittables::grid_volume::{GridVolume, VolumeGrid};
+    use crate::hittables::sphere::Sphere;
+    use crate::material::material::{Conductor, Material};
+    use crate::texture::image::Image;
+
+    fn white_furnace() -> Camera {
+        // An environment that is white in every direction. It is still importance sampled, so
+        // both halves of each bounce get exercised.
+        let white = Image::new(64, 32, vec![Color::new(1.0, 1.0, 1.0); 64 * 32]);
+        let environment = EnvironmentMap::new(Arc::new(white), 0.0, 1.0);
+        return Camera::initialize(CameraSettings {
+            num_threads: 1,
+            aspect_ratio: 1.0,
+            image_width: 16,
+            samples_per_pixel: 1,
+            max_depth: 50,
+            vfov: 30.0,
+            lookfrom: Point3::new(0.0, 0.0, 12.0),
+            lookat: Point3::new(0.0, 0.0, 0.0),
+            vup: Vec3::new(0.0, 1.0, 0.0),
+            defocus_angle: 0.0,
+            focus_dist: 10.0,
+            background: Background::Environment(Arc::new(environment)),
+        });
+    }
+
+    fn average_color(cam: &Camera, r: &Ray, world: &HittableList) -> Color {
+        let samples = 20000;
+        let mut total = Color::blank();
+        for _ in 0..samples {
+            total += cam.ray_color(r, cam.max_depth, world);
+        }
+        return total / samples as f64;
+    }
+
+    #[test]
+    fn white_volume_in_a_white_furnace_stays_white() {
+        // A volume that absorbs nothing neither adds nor removes light, so however it is
+        // sampled, every ray through it sees white.
+        let volume = GridVolume::new(
+            VolumeGrid::from_fn([8, 8, 8], |p| 0.5 + 0.5 * p.x()),
+            Point3::new(-1.0, -1.0, -1.0),
+            Vec3::new(2.0, 2.0, 2.0),
+            1.5,
+            Color::new(1.0, 1.0, 1.0),
+        );
+        let world = HittableList::single(Arc::new(volume));
+        let cam = white_furnace();
+
+        let r = Ray::new(Point3::new(0.0, 0.0, 5.0), Vec3::new(0.0, 0.0, -1.0));
+        let color = average_color(&cam, &r, &world);
+        for channel in [color.x(), color.y(), color.z()] {
+            assert!((channel - 1.0).abs() < 0.02, "got {}", channel);
+        }
+    }
+
+    fn head_on_albedo(mat: &dyn Material) -> Color {
+        // Integrates f cos over the hemisphere for light arriving along the normal, with the
+        // midpoint rule on a fine grid of polar angles and a coarser one around the normal.
+        let mut rec = HitRecord::blank();
+        rec.normal = Vec3::new(0.0, 0.0, 1.0);
+        rec.front_face = true;
+        let r_in = Ray::new(Point3::new(0.0, 0.0, 1.0), Vec3::new(0.0, 0.0, -1.0));
+
+        let (n_theta, n_phi) = (2048, 64);
+        let d_theta = 0.5 * util::PI / n_theta as f64;
+        let d_phi = 2.0 * util::PI / n_phi as f64;
+        let mut albedo = Color::blank();
+        for i in 0..n_theta {
+            let theta = (i as f64 + 0.5) * d_theta;
+            for j in 0..n_phi {
+                let phi = (j as f64 + 0.5) * d_phi;
+                let direction = Vec3::new(
+                    theta.sin() * phi.cos(),
+                    theta.sin() * phi.sin(),
+                    theta.cos(),
+                );
+                let scattered = Ray::new(rec.p, direction);
+                albedo += mat.eval(&r_in, &rec, &scattered) * theta.sin() * d_theta * d_phi;
+            }
+        }
+        return albedo;
+    }
+
+    #[test]
+    fn rough_metals_in_a_white_furnace_show_their_albedo() {
+        // A sphere never sees itself, so light leaving the front of one straight back is the
+        // albedo of its metal for light arriving head on.
+        let cam = white_furnace();
+        for mat in [
+            Conductor::silver(0.3),
+            Conductor::gold(0.6),
+            Conductor::aluminum(0.9),
+        ] {
+            let expected = head_on_albedo(&mat);
+            let sphere = Sphere::new(Point3::new(0.0, 0.0, 0.0), 1.0, Arc::new(mat));
+            let world = HittableList::single(Arc::new(sphere));
+
+            let r = Ray::new(Point3::new(0.0, 0.0, 2.0), Vec3::new(0.0, 0.0, -1.0));
+            let measured = average_color(&cam, &r, &world);
+            for (a, b) in [
+                (expected.x(), measured.x()),
+                (expected.y(), measured.y()),
+                (expected.z(), measured.z()),
+            ] {
+                assert!((a - b).abs() < 0.02, "expected {}, got {}", a, b);
+            }
+        }
+    }
+}
@@ -0,0 +1,262 @@
+use std::sync::Arc;
+
+use crate::hittables::bvh::BvhNode;
+use crate::hittables::curve::{blossom_bezier, eval_bezier};
+use crate::hittables::hittable::{HitRecord, Hittable};
+use crate::material::material::Material;
+use crate::math::aabb::AABB;
+use crate::math::interval::Interval;
+use crate::math::ray::Ray;
+use crate::math::vec3::{Point3, Vec3};
+
+struct PatchCommon {
+    cp: [[Point3; 4]; 4],
+    mat: Arc<dyn Material>,
+}
+
+// A bicubic Bézier patch. The control points are indexed cp[i][j], with i running along u
+// and j along v, and the front face is the side that Su × Sv points to. The patch is split
+// into a grid of pieces held in its own `BvhNode`, and each piece is intersected by Newton
+// iteration started from the middle of the piece.
+pub struct BezierPatch {
+    tree: BvhNode,
+}
+
+impl BezierPatch {
+    pub fn new(cp: [[Point3; 4]; 4], mat: Arc<dyn Material>) -> BezierPatch {
+        BezierPatch::new_with_pieces(cp, mat, 8)
+    }
+
+    pub fn new_with_pieces(
+        cp: [[Point3; 4]; 4],
+        mat: Arc<dyn Material>,
+        pieces: usize,
+    ) -> BezierPatch {
+        let common = Arc::new(PatchCommon { cp, mat });
+
+        // Smaller pieces give tighter boxes and starting guesses closer to the root, so
+        // Newton's method converges from the middle of any piece the ray hits.
+        let pieces = pieces.max(1);
+        let mut objects: Vec<Arc<dyn Hittable>> = Vec::with_capacity(pieces * pieces);
+        for i in 0..pieces {
+            for j in 0..pieces {
+                let u = Interval::new(i as f64 / pieces as f64, (i + 1) as f64 / pieces as f64);
+                let v = Interval::new(j as f64 / pieces as f64, (j + 1) as f64 / pieces as f64);
+                objects.push(Arc::new(PatchPiece::new(common.clone(), u, v)));
+            }
+        }
+
+        let l = objects.len();
+        BezierPatch {
+            tree: BvhNode::new(&mut objects, 0, l),
+        }
+    }
+
+    pub fn from_mesh(
+        vertices: &[Point3],
+        patches: &[[usize; 16]],
+        mat: Arc<dyn Material>,
+    ) -> Vec<Arc<dyn Hittable>> {
+        // Builds patches from a shared vertex list and rows of 16 indices, the layout the
+        // Utah teapot and similar models are distributed in.
+        let mut objects: Vec<Arc<dyn Hittable>> = Vec::with_capacity(patches.len());
+        for indices in patches {
+            let mut cp = [[Point3::blank(); 4]; 4];
+            for (k, &index) in indices.iter().enumerate() {
+                cp[k / 4][k % 4] = vertices[index];
+            }
+            objects.push(Arc::new(BezierPatch::new(cp, mat.clone())));
+        }
+        return objects;
+    }
+}
+
+impl Hittable for BezierPatch {
+    fn hit(&self, r: &Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        self.tree.hit(r, ray_t, rec)
+    }
+
+    fn bounding_box(&self) -> &AABB {
+        self.tree.bounding_box()
+    }
+}
+
+// The part of a patch covering the parameters u × v.
+struct PatchPiece {
+    common: Arc<PatchCommon>,
+    u: Interval,
+    v: Interval,
+    bbox: AABB,
+}
+
+impl PatchPiece {
+    fn new(common: Arc<PatchCommon>, u: Interval, v: Interval) -> PatchPiece {
+        // The piece lies inside the convex hull of its own control points, found by
+        // blossoming the patch first along v and then along u.
+        let rows = common.cp.map(|row| sub_curve(&row, &v));
+        let mut bbox = AABB::EMPTY;
+        for j in 0..4 {
+            let column = sub_curve(&rows.map(|row| row[j]), &u);
+            for p in column {
+                bbox = AABB::from_boxes(&bbox, &AABB::from_points(p, p));
+            }
+        }
+
+        PatchPiece { common, u, v, bbox }
+    }
+
+    fn newton(&self, r: &Ray, planes: &[(Vec3, f64); 2], u: f64, v: f64) -> Option<(f64, f64)> {
+        // Finds where the patch crosses both planes containing the ray, i.e. the root of
+        // F(u, v) = (n1·S + d1, n2·S + d2), starting from (u, v).
+        let scale = r.direction().length().max(1.0);
+        let (mut u, mut v) = (u, v);
+        for _ in 0..12 {
+            let (s, su, sv) = eval_patch(&self.common.cp, u, v);
+            let f = [
+                Vec3::dot(&planes[0].0, &s) + planes[0].1,
+                Vec3::dot(&planes[1].0, &s) + planes[1].1,
+            ];
+            if f[0].abs() < 1e-9 * scale && f[1].abs() < 1e-9 * scale {
+                return Some((u, v));
+            }
+
+            let j = [
+                [Vec3::dot(&planes[0].0, &su), Vec3::dot(&planes[0].0, &sv)],
+                [Vec3::dot(&planes[1].0, &su), Vec3::dot(&planes[1].0, &sv)],
+            ];
+            let det = j[0][0] * j[1][1] - j[0][1] * j[1][0];
+            if det.abs() < 1e-14 {
+                return None;
+            }
+            u -= (j[1][1] * f[0] - j[0][1] * f[1]) / det;
+            v -= (j[0][0] * f[1] - j[1][0] * f[0]) / det;
+
+            // Iterates that wander off the patch belong to no piece of it.
+            if !(-0.5..=1.5).contains(&u) || !(-0.5..=1.5).contains(&v) {
+                return None;
+            }
+        }
+        return None;
+    }
+}
+
+impl Hittable for PatchPiece {
+    fn hit(&self, r: &Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        if !self.bbox.hit(r, &ray_t) {
+            return false;
+        }
+
+        // Describe the ray as the intersection of two planes through it.
+        let d = Vec3::unit_vector(r.direction());
+        let n1 = if d.x().abs() > d.y().abs() && d.x().abs() > d.z().abs() {
+            Vec3::unit_vector(Vec3::new(d.y(), -d.x(), 0.0))
+        } else {
+            Vec3::unit_vector(Vec3::new(0.0, d.z(), -d.y()))
+        };
+        let n2 = Vec3::cross(n1, d);
+        let planes = [
+            (n1, -Vec3::dot(&n1, &r.origin())),
+            (n2, -Vec3::dot(&n2, &r.origin())),
+        ];
+
+        // A slight overlap between neighbouring pieces keeps rays from slipping through
+        // the seams between them.
+        let tolerance = 1e-6;
+        let u_range = Interval::new(
+            (self.u.min - tolerance).max(0.0),
+            (self.u.max + tolerance).min(1.0),
+        );
+        let v_range = Interval::new(
+            (self.v.min - tolerance).max(0.0),
+            (self.v.max + tolerance).min(1.0),
+        );
+
+        // Start from the middle of the piece, falling back on its corners if that doesn't
+        // converge to a point inside it.
+        let u_mid = 0.5 * (self.u.min + self.u.max);
+        let v_mid = 0.5 * (self.v.min + self.v.max);
+        let starts = [
+            (u_mid, v_mid),
+            (self.u.min, self.v.min),
+            (self.u.max, self.v.min),
+            (self.u.min, self.v.max),
+            (self.u.max, self.v.max),
+        ];
+
+        let mut closest = ray_t.max;
+        let mut found = None;
+        for (u0, v0) in starts {
+            if let Some((u, v)) = self.newton(r, &planes, u0, v0) {
+                if !u_range.contains(u) || !v_range.contains(v) {
+                    continue;
+                }
+                let (s, _, _) = eval_patch(&self.common.cp, u, v);
+                let t =
+                    Vec3::dot(&(s - r.origin()), &r.direction()) / r.direction().length_squared();
+                if ray_t.min < t && t < closest {
+                    closest = t;
+                    found = Some((u, v));
+                }
+            }
+        }
+
+        let (u, v) = match found {
+            Some(uv) => uv,
+            None => return false,
+        };
+
+        rec.t = closest;
+        rec.p = r.at(closest);
+        rec.u = u;
+        rec.v = v;
+        let outward_normal = patch_normal(&self.common.cp, u, v);
+        rec.set_face_normal(r, &outward_normal);
+        rec.mat = self.common.mat.clone();
+
+        return true;
+    }
+
+    fn bounding_box(&self) -> &AABB {
+        &self.bbox
+    }
+}
+
+fn sub_curve(cp: &[Point3; 4], range: &Interval) -> [Point3; 4] {
+    let (a, b) = (range.min, range.max);
+    [
+        blossom_bezier(cp, a, a, a),
+        blossom_bezier(cp, a, a, b),
+        blossom_bezier(cp, a, b, b),
+        blossom_bezier(cp, b, b, b),
+    ]
+}
+
+fn eval_patch(cp: &[[Point3; 4]; 4], u: f64, v: f64) -> (Point3, Vec3, Vec3) {
+    // Returns the point at (u, v) and the partial derivatives Su and Sv there.
+    let mut points = [Point3::blank(); 4];
+    let mut dv = [Vec3::blank(); 4];
+    for i in 0..4 {
+        (points[i], dv[i]) = eval_bezier(&cp[i], v);
+    }
+    let (s, su) = eval_bezier(&points, u);
+    let (sv, _) = eval_bezier(&dv, u);
+    return (s, su, sv);
+}
+
+fn patch_normal(cp: &[[Point3; 4]; 4], u: f64, v: f64) -> Vec3 {
+    // Where an edge of the patch collapses to a point (the pole of a dome, say), Su × Sv
+    // vanishes. The normal there is its limit from inside the patch, so nudge towards it.
+    let (_, su, sv) = eval_patch(cp, u, v);
+    let n = Vec3::cross(su, sv);
+    if !n.near_zero() {
+        return Vec3::unit_vector(n);
+    }
+
+    let nudge = 1e-4;
+    let (_, su, sv) = eval_patch(
+        cp,
+        u + nudge * (0.5 - u).signum(),
+        v + nudge * (0.5 - v).signum(),
+    );
+    return Vec3::unit_vector(Vec3::cross(su, sv));
+}
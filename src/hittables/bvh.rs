@@ -16,18 +16,46 @@ pub struct BvhNode {
 
 impl BvhNode {
     pub fn from_hittable_list(list: &mut HittableList) -> BvhNode {
-        let l = list.objects.len();
-        BvhNode::new(&mut list.objects, 0, l)
+        // Unbounded objects such as infinite planes would inflate every box they were sorted
+        // into, so they are kept out of the hierarchy and hung off the root instead.
+        let (mut bounded, unbounded): (Vec<_>, Vec<_>) = list
+            .objects
+            .iter()
+            .cloned()
+            .partition(|object| object.bounding_box().is_bounded());
+
+        if unbounded.is_empty() {
+            let l = bounded.len();
+            return BvhNode::new(&mut bounded, 0, l);
+        }
+
+        let mut unbounded_list = HittableList::new();
+        for object in unbounded {
+            unbounded_list.add(object);
+        }
+        let right: Arc<dyn Hittable> = Arc::new(unbounded_list);
+
+        // With nothing bounded the left side is left empty, rather than testing the unbounded
+        // objects twice.
+        let left: Arc<dyn Hittable> = if bounded.is_empty() {
+            Arc::new(HittableList::new())
+        } else {
+            let l = bounded.len();
+            Arc::new(BvhNode::new(&mut bounded, 0, l))
+        };
+
+        let bbox = AABB::from_boxes(left.bounding_box(), right.bounding_box());
+        BvhNode { left, right, bbox }
     }
 
     pub fn new(objects: &mut Vec<Arc<dyn Hittable>>, start: usize, end: usize) -> BvhNode {
         let left: Arc<dyn Hittable>;
         let right: Arc<dyn Hittable>;
-        let mut bbox: AABB = AABB::EMPTY.clone();
+        let mut bbox: AABB = AABB::EMPTY;
 
         // Build the bounding box of the span of source objects.
-        for object_index in start..end {
-            bbox = AABB::from_boxes(&bbox, objects[object_index].bounding_box());
+        for object in &objects[start..end] {
+            bbox = AABB::from_boxes(&bbox, object.bounding_box());
         }
 
         let axis = bbox.longest_axis();
@@ -40,7 +68,11 @@ impl BvhNode {
 
         let object_span = end - start;
 
-        if object_span == 1 {
+        if object_span == 0 {
+            // An empty span, as from a mesh with no faces, hits nothing.
+            left = Arc::new(HittableList::new());
+            right = left.clone();
+        } else if object_span == 1 {
             left = objects[start].clone();
             right = objects[start].clone();
         } else if object_span == 2 {
@@ -90,6 +122,19 @@ impl Hittable for BvhNode {
         return hit_left || hit_right;
     }
 
+    fn transmittance(&self, r: &Ray, ray_t: Interval) -> f64 {
+        if !self.bbox.hit(r, &ray_t) {
+            return 1.0;
+        }
+
+        // A node over a single object holds it on both sides, so only count it once.
+        let left = self.left.transmittance(r, ray_t);
+        if left <= 0.0 || Arc::ptr_eq(&self.left, &self.right) {
+            return left;
+        }
+        return left * self.right.transmittance(r, ray_t);
+    }
+
     fn bounding_box(&self) -> &AABB {
         &self.bbox
     }
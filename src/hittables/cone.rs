@@ -0,0 +1,128 @@
+use std::sync::Arc;
+
+use crate::hittables::disk;
+use crate::hittables::hittable::{HitRecord, Hittable};
+use crate::material::material::Material;
+use crate::math::aabb::AABB;
+use crate::math::interval::Interval;
+use crate::math::onb::Onb;
+use crate::math::ray::Ray;
+use crate::math::vec3::{Point3, Vec3};
+use crate::util;
+
+pub struct Cone {
+    base: Point3,
+    height: f64,
+    radius: f64,
+    uvw: Onb,
+    mat: Arc<dyn Material>,
+    bbox: AABB,
+}
+
+impl Cone {
+    pub fn new(base: Point3, apex: Point3, radius: f64, mat: Arc<dyn Material>) -> Cone {
+        // A cone with a capped circular base of `radius` centered on `base`, narrowing to `apex`.
+        let axis = apex - base;
+        let uvw = Onb::new(&axis);
+        let extent = disk::disk_extent(&axis, radius);
+        let base_box = AABB::from_points(base - extent, base + extent);
+        let apex_box = AABB::from_points(apex, apex);
+        Cone {
+            base,
+            height: axis.length(),
+            radius,
+            uvw,
+            mat,
+            bbox: AABB::from_boxes(&base_box, &apex_box),
+        }
+    }
+}
+
+impl Hittable for Cone {
+    fn hit(&self, r: &Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        // Work in the cone's frame, where the axis runs up z from the base to the apex at z = height.
+        // The side wall is x^2 + y^2 = (k (height - z))^2 for the slope k = radius / height.
+        let o = self.uvw.to_local(&(r.origin() - self.base));
+        let d = self.uvw.to_local(&r.direction());
+        let k2 = (self.radius / self.height) * (self.radius / self.height);
+        let q = self.height - o.z();
+
+        let mut closest = ray_t.max;
+        let mut outward_normal = Vec3::blank();
+        let mut uv = (0.0, 0.0);
+
+        // Side wall
+        let a = d.x() * d.x() + d.y() * d.y() - k2 * d.z() * d.z();
+        let h = o.x() * d.x() + o.y() * d.y() + k2 * q * d.z();
+        let c = o.x() * o.x() + o.y() * o.y() - k2 * q * q;
+        let roots = if a.abs() > 1e-12 {
+            let discriminant = h * h - a * c;
+            if discriminant < 0.0 {
+                vec![]
+            } else {
+                let sqrtd = discriminant.sqrt();
+                let (r0, r1) = ((-h - sqrtd) / a, (-h + sqrtd) / a);
+                vec![r0.min(r1), r0.max(r1)]
+            }
+        } else if h.abs() > 1e-12 {
+            // The ray runs parallel to the cone's slope and crosses the wall once.
+            vec![-c / (2.0 * h)]
+        } else {
+            vec![]
+        };
+
+        for root in roots {
+            if !Interval::new(ray_t.min, closest).surrounds(root) {
+                continue;
+            }
+            let z = o.z() + root * d.z();
+            if z < 0.0 || z > self.height {
+                continue;
+            }
+            let x = o.x() + root * d.x();
+            let y = o.y() + root * d.y();
+            let gradient = Vec3::new(x, y, k2 * (self.height - z));
+            closest = root;
+            outward_normal = if gradient.near_zero() {
+                Vec3::new(0.0, 0.0, 1.0)
+            } else {
+                Vec3::unit_vector(gradient)
+            };
+            uv = (
+                (f64::atan2(y, x) + util::PI) / (2.0 * util::PI),
+                z / self.height,
+            );
+            break;
+        }
+
+        // Base cap
+        if d.z().abs() > 1e-12 {
+            let root = -o.z() / d.z();
+            let x = o.x() + root * d.x();
+            let y = o.y() + root * d.y();
+            if Interval::new(ray_t.min, closest).surrounds(root)
+                && x * x + y * y <= self.radius * self.radius
+            {
+                closest = root;
+                outward_normal = Vec3::new(0.0, 0.0, -1.0);
+                uv = disk::polar_uv(x, y, self.radius);
+            }
+        }
+
+        if closest >= ray_t.max {
+            return false;
+        }
+
+        rec.t = closest;
+        rec.p = r.at(closest);
+        (rec.u, rec.v) = uv;
+        rec.set_face_normal(r, &self.uvw.transform(&outward_normal));
+        rec.mat = self.mat.clone();
+
+        return true;
+    }
+
+    fn bounding_box(&self) -> &AABB {
+        &self.bbox
+    }
+}
@@ -0,0 +1,90 @@
+use std::sync::Arc;
+
+use crate::color::Color;
+use crate::hittables::hittable::{HitRecord, Hittable};
+use crate::material::material::{Isotropic, Material};
+use crate::math::aabb::AABB;
+use crate::math::interval::Interval;
+use crate::math::ray::Ray;
+use crate::texture::texture::{SolidColor, Texture};
+use crate::util;
+
+// A volume of uniform density filling a closed boundary, like fog or smoke. A ray passing
+// through it scatters somewhere inside with a probability that grows with the distance it
+// travels there.
+pub struct ConstantMedium {
+    boundary: Arc<dyn Hittable>,
+    neg_inv_density: f64,
+    phase_function: Arc<dyn Material>,
+}
+
+impl ConstantMedium {
+    pub fn new(boundary: Arc<dyn Hittable>, density: f64, albedo: Color) -> ConstantMedium {
+        ConstantMedium::from_texture(boundary, density, Arc::new(SolidColor::new(albedo)))
+    }
+
+    pub fn from_texture(
+        boundary: Arc<dyn Hittable>,
+        density: f64,
+        tex: Arc<dyn Texture>,
+    ) -> ConstantMedium {
+        ConstantMedium {
+            boundary,
+            neg_inv_density: -1.0 / density,
+            phase_function: Arc::new(Isotropic::from_texture(tex)),
+        }
+    }
+}
+
+impl Hittable for ConstantMedium {
+    fn hit(&self, r: &Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        // Find where the ray's line enters and then leaves the boundary. This assumes the
+        // boundary is convex along the ray: the medium between any later re-entry and exit
+        // is missed.
+        let mut rec1 = HitRecord::blank();
+        let mut rec2 = HitRecord::blank();
+
+        if !self.boundary.hit(r, Interval::UNIVERSE, &mut rec1) {
+            return false;
+        }
+
+        if !self
+            .boundary
+            .hit(r, Interval::new(rec1.t + 0.0001, util::INFINITY), &mut rec2)
+        {
+            return false;
+        }
+
+        if rec1.t < ray_t.min {
+            rec1.t = ray_t.min;
+        }
+        if rec2.t > ray_t.max {
+            rec2.t = ray_t.max;
+        }
+
+        if rec1.t >= rec2.t {
+            return false;
+        }
+
+        if rec1.t < 0.0 {
+            rec1.t = 0.0;
+        }
+
+        // Sample how far the ray travels through the medium before it scatters.
+        let ray_length = r.direction().length();
+        let distance_inside_boundary = (rec2.t - rec1.t) * ray_length;
+        let hit_distance = self.neg_inv_density * util::random_double().ln();
+
+        if hit_distance > distance_inside_boundary {
+            return false;
+        }
+
+        rec.set_medium_scatter(r, rec1.t + hit_distance / ray_length, &self.phase_function);
+
+        return true;
+    }
+
+    fn bounding_box(&self) -> &AABB {
+        self.boundary.bounding_box()
+    }
+}
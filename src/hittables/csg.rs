@@ -0,0 +1,222 @@
+use std::sync::Arc;
+
+use crate::hittables::hittable::{HitRecord, HitSpan, Hittable};
+use crate::math::aabb::AABB;
+use crate::math::interval::Interval;
+use crate::math::ray::Ray;
+
+#[derive(Clone, Copy)]
+enum CsgOp {
+    Union,
+    Intersection,
+    Difference,
+}
+
+impl CsgOp {
+    fn inside(self, in_a: bool, in_b: bool) -> bool {
+        match self {
+            CsgOp::Union => in_a || in_b,
+            CsgOp::Intersection => in_a && in_b,
+            CsgOp::Difference => in_a && !in_b,
+        }
+    }
+}
+
+struct CsgEvent<'a> {
+    rec: &'a HitRecord,
+    entering: bool,
+    from_a: bool,
+}
+
+fn csg_spans(
+    op: CsgOp,
+    a: &dyn Hittable,
+    b: &dyn Hittable,
+    r: &Ray,
+    ray_t: Interval,
+    spans: &mut Vec<HitSpan>,
+) {
+    // Whether the ray starts inside either operand depends on everything behind `ray_t`, so
+    // gather the operands' spans from the far end of the ray.
+    let operand_t = Interval::new(f64::NEG_INFINITY, ray_t.max);
+    let mut a_spans = Vec::new();
+    let mut b_spans = Vec::new();
+    a.hit_spans(r, operand_t, &mut a_spans);
+    b.hit_spans(r, operand_t, &mut b_spans);
+
+    let mut events = Vec::with_capacity(2 * (a_spans.len() + b_spans.len()));
+    for (operand, from_a) in [(&a_spans, true), (&b_spans, false)] {
+        for span in operand.iter() {
+            events.push(CsgEvent {
+                rec: &span.enter,
+                entering: true,
+                from_a,
+            });
+            events.push(CsgEvent {
+                rec: &span.exit,
+                entering: false,
+                from_a,
+            });
+        }
+    }
+    events.sort_by(|x, y| x.rec.t.total_cmp(&y.rec.t));
+
+    // Sweep along the ray, emitting a boundary whenever the combined solid is entered or left.
+    // Each boundary keeps the hit record, and so the material, of the operand that caused it.
+    // Hit normals already face the incoming ray, so a surface of `b` that bounds a difference
+    // only needs its front face flag updated.
+    let mut in_a = false;
+    let mut in_b = false;
+    let mut inside = false;
+    let mut enter: Option<HitRecord> = None;
+
+    for event in events {
+        if event.from_a {
+            in_a = event.entering;
+        } else {
+            in_b = event.entering;
+        }
+
+        let now_inside = op.inside(in_a, in_b);
+        if now_inside == inside {
+            continue;
+        }
+        inside = now_inside;
+
+        let mut rec = event.rec.clone();
+        rec.front_face = now_inside;
+        if now_inside {
+            enter = Some(rec);
+        } else {
+            let enter = enter.take().unwrap_or_else(|| HitRecord {
+                t: f64::NEG_INFINITY,
+                ..rec.clone()
+            });
+            if rec.t > ray_t.min {
+                spans.push(HitSpan { enter, exit: rec });
+            }
+        }
+    }
+
+    if let Some(enter) = enter {
+        let exit = HitRecord {
+            t: f64::INFINITY,
+            ..enter.clone()
+        };
+        spans.push(HitSpan { enter, exit });
+    }
+}
+
+fn csg_hit(
+    op: CsgOp,
+    a: &dyn Hittable,
+    b: &dyn Hittable,
+    r: &Ray,
+    ray_t: Interval,
+    rec: &mut HitRecord,
+) -> bool {
+    let mut spans = Vec::new();
+    csg_spans(op, a, b, r, ray_t, &mut spans);
+
+    // Spans are ordered, so the first boundary inside the interval is the nearest hit.
+    for span in spans {
+        for boundary in [span.enter, span.exit] {
+            if ray_t.surrounds(boundary.t) {
+                *rec = boundary;
+                return true;
+            }
+        }
+    }
+    return false;
+}
+
+pub struct CsgUnion {
+    a: Arc<dyn Hittable>,
+    b: Arc<dyn Hittable>,
+    bbox: AABB,
+}
+
+impl CsgUnion {
+    pub fn new(a: Arc<dyn Hittable>, b: Arc<dyn Hittable>) -> CsgUnion {
+        let bbox = AABB::from_boxes(a.bounding_box(), b.bounding_box());
+        CsgUnion { a, b, bbox }
+    }
+}
+
+impl Hittable for CsgUnion {
+    fn hit(&self, r: &Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        if !self.bbox.hit(r, &ray_t) {
+            return false;
+        }
+        csg_hit(CsgOp::Union, &*self.a, &*self.b, r, ray_t, rec)
+    }
+
+    fn bounding_box(&self) -> &AABB {
+        &self.bbox
+    }
+
+    fn hit_spans(&self, r: &Ray, ray_t: Interval, spans: &mut Vec<HitSpan>) {
+        csg_spans(CsgOp::Union, &*self.a, &*self.b, r, ray_t, spans);
+    }
+}
+
+pub struct CsgIntersection {
+    a: Arc<dyn Hittable>,
+    b: Arc<dyn Hittable>,
+    bbox: AABB,
+}
+
+impl CsgIntersection {
+    pub fn new(a: Arc<dyn Hittable>, b: Arc<dyn Hittable>) -> CsgIntersection {
+        let bbox = AABB::from_overlap(a.bounding_box(), b.bounding_box());
+        CsgIntersection { a, b, bbox }
+    }
+}
+
+impl Hittable for CsgIntersection {
+    fn hit(&self, r: &Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        if !self.bbox.hit(r, &ray_t) {
+            return false;
+        }
+        csg_hit(CsgOp::Intersection, &*self.a, &*self.b, r, ray_t, rec)
+    }
+
+    fn bounding_box(&self) -> &AABB {
+        &self.bbox
+    }
+
+    fn hit_spans(&self, r: &Ray, ray_t: Interval, spans: &mut Vec<HitSpan>) {
+        csg_spans(CsgOp::Intersection, &*self.a, &*self.b, r, ray_t, spans);
+    }
+}
+
+pub struct CsgDifference {
+    a: Arc<dyn Hittable>,
+    b: Arc<dyn Hittable>,
+    bbox: AABB,
+}
+
+impl CsgDifference {
+    pub fn new(a: Arc<dyn Hittable>, b: Arc<dyn Hittable>) -> CsgDifference {
+        // `a` with `b` cut out of it. The result never extends beyond `a`.
+        let bbox = *a.bounding_box();
+        CsgDifference { a, b, bbox }
+    }
+}
+
+impl Hittable for CsgDifference {
+    fn hit(&self, r: &Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        if !self.bbox.hit(r, &ray_t) {
+            return false;
+        }
+        csg_hit(CsgOp::Difference, &*self.a, &*self.b, r, ray_t, rec)
+    }
+
+    fn bounding_box(&self) -> &AABB {
+        &self.bbox
+    }
+
+    fn hit_spans(&self, r: &Ray, ray_t: Interval, spans: &mut Vec<HitSpan>) {
+        csg_spans(CsgOp::Difference, &*self.a, &*self.b, r, ray_t, spans);
+    }
+}
@@ -0,0 +1,94 @@
+use std::sync::Arc;
+
+use crate::hittables::hittable::{HitRecord, Hittable};
+use crate::material::material::Material;
+use crate::math::aabb::AABB;
+use crate::math::interval::Interval;
+use crate::math::ray::Ray;
+use crate::math::vec3::{Point3, Vec3};
+
+pub struct Cuboid {
+    mat: Arc<dyn Material>,
+    bbox: AABB,
+}
+
+impl Cuboid {
+    pub fn new(a: Point3, b: Point3, mat: Arc<dyn Material>) -> Cuboid {
+        // A solid axis-aligned box with opposite corners `a` and `b`.
+        Cuboid {
+            mat,
+            bbox: AABB::from_points(a, b),
+        }
+    }
+}
+
+impl Hittable for Cuboid {
+    fn hit(&self, r: &Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        // Slab test, remembering which axis the ray enters and leaves through.
+        let mut t_enter = f64::NEG_INFINITY;
+        let mut t_exit = f64::INFINITY;
+        let mut enter_axis = 0;
+        let mut exit_axis = 0;
+
+        for axis in 0..3 {
+            let ax = self.bbox.axis_interval(axis);
+            let adinv = 1.0 / r.direction()[axis];
+
+            let t0 = (ax.min - r.origin()[axis]) * adinv;
+            let t1 = (ax.max - r.origin()[axis]) * adinv;
+            let (near, far) = if t0 < t1 { (t0, t1) } else { (t1, t0) };
+
+            if near > t_enter {
+                t_enter = near;
+                enter_axis = axis;
+            }
+            if far < t_exit {
+                t_exit = far;
+                exit_axis = axis;
+            }
+        }
+
+        if t_enter > t_exit {
+            return false;
+        }
+
+        let (root, axis) = if ray_t.surrounds(t_enter) {
+            (t_enter, enter_axis)
+        } else if ray_t.surrounds(t_exit) {
+            (t_exit, exit_axis)
+        } else {
+            return false;
+        };
+
+        rec.t = root;
+        rec.p = r.at(root);
+
+        // The face normal points along the hit axis, toward whichever side of the box was hit.
+        let ax = self.bbox.axis_interval(axis);
+        let side = if rec.p[axis] - ax.min < ax.max - rec.p[axis] {
+            -1.0
+        } else {
+            1.0
+        };
+        let mut outward_normal = [0.0; 3];
+        outward_normal[axis] = side;
+        let outward_normal = Vec3::new(outward_normal[0], outward_normal[1], outward_normal[2]);
+
+        // Map the face onto [0,1] along its two other axes.
+        let u_axis = (axis + 1) % 3;
+        let v_axis = (axis + 2) % 3;
+        let u_interval = self.bbox.axis_interval(u_axis);
+        let v_interval = self.bbox.axis_interval(v_axis);
+        rec.u = (rec.p[u_axis] - u_interval.min) / u_interval.size();
+        rec.v = (rec.p[v_axis] - v_interval.min) / v_interval.size();
+
+        rec.set_face_normal(r, &outward_normal);
+        rec.mat = self.mat.clone();
+
+        return true;
+    }
+
+    fn bounding_box(&self) -> &AABB {
+        &self.bbox
+    }
+}
@@ -0,0 +1,366 @@
+use std::sync::Arc;
+
+use crate::hittables::hittable::{HitRecord, Hittable};
+use crate::material::material::Material;
+use crate::math::aabb::AABB;
+use crate::math::interval::Interval;
+use crate::math::mat4::Mat4;
+use crate::math::ray::Ray;
+use crate::math::vec3::{Point3, Vec3};
+
+#[derive(Clone, Copy)]
+pub enum CurveShape {
+    Ribbon,   // A flat strip that always faces the incoming ray
+    Cylinder, // A ribbon whose normal bends across its width to shade like a tube
+}
+
+struct CurveCommon {
+    cp: [Point3; 4],
+    width: [f64; 2],
+    shape: CurveShape,
+    mat: Arc<dyn Material>,
+}
+
+// One piece of a cubic Bézier curve, covering the curve parameters [u_min, u_max]. Long
+// curves are split into several pieces so each gets a tight box of its own in a `BvhNode`.
+pub struct Curve {
+    common: Arc<CurveCommon>,
+    cp: [Point3; 4], // Control points of just this piece
+    u_min: f64,
+    u_max: f64,
+    bbox: AABB,
+}
+
+impl Curve {
+    pub fn create(
+        cp: [Point3; 4],
+        width0: f64,
+        width1: f64,
+        shape: CurveShape,
+        mat: Arc<dyn Material>,
+        segments: usize,
+    ) -> Vec<Arc<dyn Hittable>> {
+        // Builds a curve through the Bézier control points `cp`, tapering linearly from
+        // `width0` at the start to `width1` at the end, split into `segments` pieces.
+        let common = Arc::new(CurveCommon {
+            cp,
+            width: [width0, width1],
+            shape,
+            mat,
+        });
+
+        let segments = segments.max(1);
+        let mut pieces: Vec<Arc<dyn Hittable>> = Vec::with_capacity(segments);
+        for i in 0..segments {
+            let u_min = i as f64 / segments as f64;
+            let u_max = (i + 1) as f64 / segments as f64;
+            pieces.push(Arc::new(Curve::new(common.clone(), u_min, u_max)));
+        }
+        return pieces;
+    }
+
+    fn new(common: Arc<CurveCommon>, u_min: f64, u_max: f64) -> Curve {
+        let p = &common.cp;
+        let cp = [
+            blossom_bezier(p, u_min, u_min, u_min),
+            blossom_bezier(p, u_min, u_min, u_max),
+            blossom_bezier(p, u_min, u_max, u_max),
+            blossom_bezier(p, u_max, u_max, u_max),
+        ];
+
+        // The piece lies within the hull of its control points, grown by half its width.
+        let half_width = 0.5 * common.width[0].max(common.width[1]);
+        let pad = Vec3::new(half_width, half_width, half_width);
+        let mut bbox = AABB::EMPTY;
+        for point in cp.iter() {
+            bbox = AABB::from_boxes(&bbox, &AABB::from_points(*point - pad, *point + pad));
+        }
+
+        Curve {
+            common,
+            cp,
+            u_min,
+            u_max,
+            bbox,
+        }
+    }
+
+    fn width_at(&self, u: f64) -> f64 {
+        (1.0 - u) * self.common.width[0] + u * self.common.width[1]
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn recursive_intersect(
+        &self,
+        ray_frame: &RayFrame,
+        cp: &[Point3; 4],
+        u0: f64,
+        u1: f64,
+        depth: i64,
+        ray_t: Interval,
+        closest: &mut f64,
+        rec: &mut HitRecord,
+    ) -> bool {
+        let ray_length = ray_frame.length;
+
+        if depth > 0 {
+            // Split the curve in half and try whichever halves the ray could pass through.
+            let split = subdivide_bezier(cp);
+            let halves = [
+                [split[0], split[1], split[2], split[3]],
+                [split[3], split[4], split[5], split[6]],
+            ];
+            let u = [u0, (u0 + u1) / 2.0, u1];
+
+            let mut hit_anything = false;
+            for (seg, half) in halves.iter().enumerate() {
+                let max_width = self.width_at(u[seg]).max(self.width_at(u[seg + 1]));
+                let z_range = Interval::new(ray_t.min * ray_length, *closest * ray_length);
+                if !overlaps_ray(half, 0.5 * max_width, &z_range) {
+                    continue;
+                }
+                if self.recursive_intersect(
+                    ray_frame,
+                    half,
+                    u[seg],
+                    u[seg + 1],
+                    depth - 1,
+                    ray_t,
+                    closest,
+                    rec,
+                ) {
+                    hit_anything = true;
+                }
+            }
+            return hit_anything;
+        }
+
+        // Treat the remaining piece as a line segment. In ray space the ray is the z axis, so
+        // first reject hits beyond either end of the segment.
+        let edge = (cp[1].y() - cp[0].y()) * -cp[0].y() + cp[0].x() * (cp[0].x() - cp[1].x());
+        if edge < 0.0 {
+            return false;
+        }
+        let edge = (cp[2].y() - cp[3].y()) * -cp[3].y() + cp[3].x() * (cp[3].x() - cp[2].x());
+        if edge < 0.0 {
+            return false;
+        }
+
+        // Find the point along the segment closest to the ray.
+        let segment_x = cp[3].x() - cp[0].x();
+        let segment_y = cp[3].y() - cp[0].y();
+        let denom = segment_x * segment_x + segment_y * segment_y;
+        if denom == 0.0 {
+            return false;
+        }
+        let w = (-cp[0].x() * segment_x - cp[0].y() * segment_y) / denom;
+
+        // Compute the u coordinate of the hit and the curve's width there.
+        let u = ((1.0 - w) * u0 + w * u1).clamp(u0, u1);
+        let hit_width = self.width_at(u);
+
+        // Test the hit against the curve's width.
+        let (pc, dpcdw) = eval_bezier(cp, w.clamp(0.0, 1.0));
+        let pt_curve_dist2 = pc.x() * pc.x() + pc.y() * pc.y();
+        if pt_curve_dist2 > hit_width * hit_width * 0.25 {
+            return false;
+        }
+
+        let t = pc.z() / ray_length;
+        if !Interval::new(ray_t.min, *closest).surrounds(t) {
+            return false;
+        }
+
+        // v runs across the curve's width, from 0 on one edge to 1 on the other.
+        let pt_curve_dist = pt_curve_dist2.sqrt();
+        let edge_func = dpcdw.x() * -pc.y() + pc.x() * dpcdw.y();
+        let v = if edge_func > 0.0 {
+            0.5 + pt_curve_dist / hit_width
+        } else {
+            0.5 - pt_curve_dist / hit_width
+        };
+
+        // The surface spans the tangent and a direction across the curve, perpendicular to
+        // the ray. Cylinders rotate that direction about the tangent so the normal sweeps
+        // around the tube from one edge to the other.
+        let (_, dpdu) = eval_bezier(&self.common.cp, u);
+        let dpdu_plane = ray_frame.to_ray_space(&dpdu);
+        let mut dpdv_plane = Vec3::unit_vector(Vec3::new(-dpdu_plane.y(), dpdu_plane.x(), 0.0));
+        if let CurveShape::Cylinder = self.common.shape {
+            let theta = -90.0 + 180.0 * v;
+            dpdv_plane = Mat4::rotation(dpdu_plane, -theta).transform_vector(&dpdv_plane);
+        }
+        let dpdv = ray_frame.to_world_space(&dpdv_plane);
+
+        *closest = t;
+        rec.t = t;
+        rec.p = ray_frame.ray.at(t);
+        rec.u = u;
+        rec.v = v;
+        let outward_normal = Vec3::unit_vector(Vec3::cross(dpdu, dpdv));
+        rec.set_face_normal(&ray_frame.ray, &outward_normal);
+        rec.mat = self.common.mat.clone();
+        return true;
+    }
+}
+
+impl Hittable for Curve {
+    fn hit(&self, r: &Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        // Move the control points into a frame where the ray starts at the origin and runs
+        // down +z, oriented so the curve's chord lies roughly along +y.
+        let chord = self.cp[3] - self.cp[0];
+        let ray_frame = RayFrame::new(r, &chord);
+        let cp = self.cp.map(|p| ray_frame.point_to_ray_space(&p));
+
+        let max_width = self.width_at(self.u_min).max(self.width_at(self.u_max));
+        let z_range = Interval::new(ray_t.min * ray_frame.length, ray_t.max * ray_frame.length);
+        if !overlaps_ray(&cp, 0.5 * max_width, &z_range) {
+            return false;
+        }
+
+        // Subdivide until the pieces are flat to within a small fraction of the curve's width.
+        let mut l0: f64 = 0.0;
+        for i in 0..2 {
+            let second_difference = cp[i] - 2.0 * cp[i + 1] + cp[i + 2];
+            l0 = l0
+                .max(second_difference.x().abs())
+                .max(second_difference.y().abs())
+                .max(second_difference.z().abs());
+        }
+        let eps = self.common.width[0].max(self.common.width[1]) * 0.05;
+        let r0 = (std::f64::consts::SQRT_2 * 6.0 * l0 / (8.0 * eps)).log2() / 2.0;
+        let max_depth = if r0.is_finite() {
+            r0.round().clamp(0.0, 10.0) as i64
+        } else {
+            0
+        };
+
+        let mut closest = ray_t.max;
+        return self.recursive_intersect(
+            &ray_frame,
+            &cp,
+            self.u_min,
+            self.u_max,
+            max_depth,
+            ray_t,
+            &mut closest,
+            rec,
+        );
+    }
+
+    fn bounding_box(&self) -> &AABB {
+        &self.bbox
+    }
+}
+
+struct RayFrame {
+    ray: Ray,
+    length: f64,
+    axis: [Vec3; 3],
+}
+
+impl RayFrame {
+    fn new(r: &Ray, up_hint: &Vec3) -> RayFrame {
+        let length = r.direction().length();
+        let dir = r.direction() / length;
+        let mut dx = Vec3::cross(dir, *up_hint);
+        if dx.near_zero() {
+            dx = if dir.x().abs() > 0.9 {
+                Vec3::cross(dir, Vec3::new(0.0, 1.0, 0.0))
+            } else {
+                Vec3::cross(dir, Vec3::new(1.0, 0.0, 0.0))
+            };
+        }
+        let right = Vec3::unit_vector(Vec3::cross(dx, dir));
+        let up = Vec3::cross(dir, right);
+        RayFrame {
+            ray: Ray::new_timed(r.origin(), r.direction(), r.time()),
+            length,
+            axis: [right, up, dir],
+        }
+    }
+
+    fn point_to_ray_space(&self, p: &Point3) -> Point3 {
+        self.to_ray_space(&(*p - self.ray.origin()))
+    }
+
+    fn to_ray_space(&self, v: &Vec3) -> Vec3 {
+        Vec3::new(
+            Vec3::dot(v, &self.axis[0]),
+            Vec3::dot(v, &self.axis[1]),
+            Vec3::dot(v, &self.axis[2]),
+        )
+    }
+
+    fn to_world_space(&self, v: &Vec3) -> Vec3 {
+        v[0] * self.axis[0] + v[1] * self.axis[1] + v[2] * self.axis[2]
+    }
+}
+
+fn overlaps_ray(cp: &[Point3; 4], half_width: f64, z_range: &Interval) -> bool {
+    // In ray space the ray is the z axis, so a piece can only be hit if its padded bounds
+    // straddle x = 0 and y = 0 within the ray's extent.
+    for axis in 0..3 {
+        let mut min = f64::INFINITY;
+        let mut max = f64::NEG_INFINITY;
+        for p in cp.iter() {
+            min = min.min(p[axis]);
+            max = max.max(p[axis]);
+        }
+        min -= half_width;
+        max += half_width;
+
+        let (lo, hi) = if axis == 2 {
+            (z_range.min, z_range.max)
+        } else {
+            (0.0, 0.0)
+        };
+        if max < lo || min > hi {
+            return false;
+        }
+    }
+    return true;
+}
+
+fn lerp(t: f64, a: Point3, b: Point3) -> Point3 {
+    (1.0 - t) * a + t * b
+}
+
+pub fn blossom_bezier(p: &[Point3; 4], u0: f64, u1: f64, u2: f64) -> Point3 {
+    let a = [
+        lerp(u0, p[0], p[1]),
+        lerp(u0, p[1], p[2]),
+        lerp(u0, p[2], p[3]),
+    ];
+    let b = [lerp(u1, a[0], a[1]), lerp(u1, a[1], a[2])];
+    return lerp(u2, b[0], b[1]);
+}
+
+fn subdivide_bezier(cp: &[Point3; 4]) -> [Point3; 7] {
+    [
+        cp[0],
+        (cp[0] + cp[1]) / 2.0,
+        (cp[0] + 2.0 * cp[1] + cp[2]) / 4.0,
+        (cp[0] + 3.0 * cp[1] + 3.0 * cp[2] + cp[3]) / 8.0,
+        (cp[1] + 2.0 * cp[2] + cp[3]) / 4.0,
+        (cp[2] + cp[3]) / 2.0,
+        cp[3],
+    ]
+}
+
+pub fn eval_bezier(cp: &[Point3; 4], u: f64) -> (Point3, Vec3) {
+    // Returns the point at u and the derivative there, by de Casteljau's algorithm.
+    let cp1 = [
+        lerp(u, cp[0], cp[1]),
+        lerp(u, cp[1], cp[2]),
+        lerp(u, cp[2], cp[3]),
+    ];
+    let cp2 = [lerp(u, cp1[0], cp1[1]), lerp(u, cp1[1], cp1[2])];
+    let deriv = if (cp2[1] - cp2[0]).length_squared() > 0.0 {
+        3.0 * (cp2[1] - cp2[0])
+    } else {
+        // For a degenerate curve the derivative vanishes at the ends, so use the chord.
+        cp[3] - cp[0]
+    };
+    return (lerp(u, cp2[0], cp2[1]), deriv);
+}
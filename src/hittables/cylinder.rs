@@ -0,0 +1,114 @@
+use std::sync::Arc;
+
+use crate::hittables::disk;
+use crate::hittables::hittable::{HitRecord, Hittable};
+use crate::material::material::Material;
+use crate::math::aabb::AABB;
+use crate::math::interval::Interval;
+use crate::math::onb::Onb;
+use crate::math::ray::Ray;
+use crate::math::vec3::{Point3, Vec3};
+use crate::util;
+
+pub struct Cylinder {
+    base: Point3,
+    height: f64,
+    radius: f64,
+    uvw: Onb,
+    mat: Arc<dyn Material>,
+    bbox: AABB,
+}
+
+impl Cylinder {
+    pub fn new(base: Point3, top: Point3, radius: f64, mat: Arc<dyn Material>) -> Cylinder {
+        // A cylinder capped at both ends, running from the center of `base` to the center of `top`.
+        let axis = top - base;
+        let uvw = Onb::new(&axis);
+        let extent = disk::disk_extent(&axis, radius);
+        let base_box = AABB::from_points(base - extent, base + extent);
+        let top_box = AABB::from_points(top - extent, top + extent);
+        Cylinder {
+            base,
+            height: axis.length(),
+            radius,
+            uvw,
+            mat,
+            bbox: AABB::from_boxes(&base_box, &top_box),
+        }
+    }
+}
+
+impl Hittable for Cylinder {
+    fn hit(&self, r: &Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        // Work in the cylinder's frame, where the axis runs up z from the origin.
+        let o = self.uvw.to_local(&(r.origin() - self.base));
+        let d = self.uvw.to_local(&r.direction());
+
+        let mut closest = ray_t.max;
+        let mut outward_normal = Vec3::blank();
+        let mut uv = (0.0, 0.0);
+
+        // Side wall
+        let a = d.x() * d.x() + d.y() * d.y();
+        if a > 1e-12 {
+            let h = o.x() * d.x() + o.y() * d.y();
+            let c = o.x() * o.x() + o.y() * o.y() - self.radius * self.radius;
+            let discriminant = h * h - a * c;
+            if discriminant >= 0.0 {
+                let sqrtd = discriminant.sqrt();
+                for root in [(-h - sqrtd) / a, (-h + sqrtd) / a] {
+                    if !Interval::new(ray_t.min, closest).surrounds(root) {
+                        continue;
+                    }
+                    let z = o.z() + root * d.z();
+                    if z < 0.0 || z > self.height {
+                        continue;
+                    }
+                    let x = o.x() + root * d.x();
+                    let y = o.y() + root * d.y();
+                    closest = root;
+                    outward_normal = Vec3::new(x / self.radius, y / self.radius, 0.0);
+                    uv = (
+                        (f64::atan2(y, x) + util::PI) / (2.0 * util::PI),
+                        z / self.height,
+                    );
+                    break;
+                }
+            }
+        }
+
+        // End caps
+        if d.z().abs() > 1e-12 {
+            for (cap_z, cap_normal) in [(0.0, -1.0), (self.height, 1.0)] {
+                let root = (cap_z - o.z()) / d.z();
+                if !Interval::new(ray_t.min, closest).surrounds(root) {
+                    continue;
+                }
+                let x = o.x() + root * d.x();
+                let y = o.y() + root * d.y();
+                if x * x + y * y > self.radius * self.radius {
+                    continue;
+                }
+                closest = root;
+                outward_normal = Vec3::new(0.0, 0.0, cap_normal);
+                uv = disk::polar_uv(x, y, self.radius);
+            }
+        }
+
+        if closest >= ray_t.max {
+            return false;
+        }
+
+        rec.t = closest;
+        rec.p = r.at(closest);
+        (rec.u, rec.v) = uv;
+        rec.set_face_normal(r, &self.uvw.transform(&outward_normal));
+        rec.mat = self.mat.clone();
+
+        return true;
+    }
+
+    fn bounding_box(&self) -> &AABB {
+        &self.bbox
+    }
+}
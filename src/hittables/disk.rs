@@ -0,0 +1,86 @@
+use std::sync::Arc;
+
+use crate::hittables::hittable::{HitRecord, Hittable};
+use crate::material::material::Material;
+use crate::math::aabb::AABB;
+use crate::math::interval::Interval;
+use crate::math::onb::Onb;
+use crate::math::ray::Ray;
+use crate::math::vec3::{Point3, Vec3};
+use crate::util;
+
+pub struct Disk {
+    center: Point3,
+    radius: f64,
+    uvw: Onb,
+    mat: Arc<dyn Material>,
+    bbox: AABB,
+}
+
+impl Disk {
+    pub fn new(center: Point3, normal: Vec3, radius: f64, mat: Arc<dyn Material>) -> Disk {
+        let uvw = Onb::new(&normal);
+        let extent = disk_extent(&uvw.w(), radius);
+        Disk {
+            center,
+            radius,
+            uvw,
+            mat,
+            bbox: AABB::from_points(center - extent, center + extent),
+        }
+    }
+}
+
+impl Hittable for Disk {
+    fn hit(&self, r: &Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        let normal = self.uvw.w();
+        let denom = Vec3::dot(&normal, &r.direction());
+
+        // No hit if the ray is parallel to the disk.
+        if denom.abs() < 1e-8 {
+            return false;
+        }
+
+        let t = Vec3::dot(&normal, &(self.center - r.origin())) / denom;
+        if !ray_t.surrounds(t) {
+            return false;
+        }
+
+        let p = r.at(t);
+        let planar = self.uvw.to_local(&(p - self.center));
+        let dist_squared = planar.x() * planar.x() + planar.y() * planar.y();
+        if dist_squared > self.radius * self.radius {
+            return false;
+        }
+
+        rec.t = t;
+        rec.p = p;
+        (rec.u, rec.v) = polar_uv(planar.x(), planar.y(), self.radius);
+        rec.set_face_normal(r, &normal);
+        rec.mat = self.mat.clone();
+
+        return true;
+    }
+
+    fn bounding_box(&self) -> &AABB {
+        &self.bbox
+    }
+}
+
+pub fn disk_extent(normal: &Vec3, radius: f64) -> Vec3 {
+    // Half-extents of a disk's bounding box. A disk only spans an axis as far as it leans
+    // away from it, so a disk facing down +Y is flat in Y and `radius` wide in X and Z.
+    let n = Vec3::unit_vector(*normal);
+    return Vec3::new(
+        radius * (1.0 - n.x() * n.x()).max(0.0).sqrt(),
+        radius * (1.0 - n.y() * n.y()).max(0.0).sqrt(),
+        radius * (1.0 - n.z() * n.z()).max(0.0).sqrt(),
+    );
+}
+
+pub fn polar_uv(x: f64, y: f64, radius: f64) -> (f64, f64) {
+    // u: angle around the disk normal in [0,1], v: distance from the center in [0,1].
+    let phi = f64::atan2(y, x) + util::PI;
+    let dist = (x * x + y * y).sqrt();
+    return (phi / (2.0 * util::PI), dist / radius);
+}
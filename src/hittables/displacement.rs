@@ -0,0 +1,180 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::hittables::mesh::{edge_key, Face, Mesh};
+use crate::math::vec3::Vec3;
+use crate::texture::texture::Texture;
+
+pub fn triangulate(mesh: &Mesh) -> Mesh {
+    // Fans every polygon out from its first corner.
+    let mut faces = Vec::with_capacity(mesh.faces.len());
+    for face in mesh.faces.iter() {
+        let k = face.vertices.len();
+        for i in 1..k.saturating_sub(1) {
+            let vertices = vec![face.vertices[0], face.vertices[i], face.vertices[i + 1]];
+            if face.uvs.is_empty() {
+                faces.push(Face::new(vertices));
+            } else {
+                let uvs = vec![face.uvs[0], face.uvs[i], face.uvs[i + 1]];
+                faces.push(Face::with_uvs(vertices, uvs));
+            }
+        }
+    }
+
+    let mut triangulated = Mesh::new(mesh.positions.clone(), mesh.uvs.clone(), faces);
+    triangulated.creases = mesh.creases.clone();
+    return triangulated;
+}
+
+pub fn tessellate(mesh: &Mesh, max_edge: f64) -> Mesh {
+    // Splits edges at their midpoints until none is longer than `max_edge`, leaving the
+    // shape unchanged. Edges are split regardless of which triangle asks, so neighbouring
+    // triangles always agree and no cracks open up between them.
+    let mut mesh = triangulate(mesh);
+    for _ in 0..32 {
+        match split_long_edges(&mesh, max_edge) {
+            Some(split) => mesh = split,
+            None => break,
+        }
+    }
+    return mesh;
+}
+
+fn split_long_edges(mesh: &Mesh, max_edge: f64) -> Option<Mesh> {
+    let (edges, _) = mesh.edges();
+
+    let mut positions = mesh.positions.clone();
+    let mut midpoints = HashMap::new();
+    let mut creases: HashSet<(usize, usize)> = mesh.creases.clone();
+    for edge in edges.iter() {
+        let a = mesh.positions[edge.a];
+        let b = mesh.positions[edge.b];
+        if (b - a).length() <= max_edge {
+            continue;
+        }
+        positions.push(0.5 * (a + b));
+        let m = positions.len() - 1;
+        let key = edge_key(edge.a, edge.b);
+        midpoints.insert(key, m);
+        if creases.remove(&key) {
+            creases.insert(edge_key(edge.a, m));
+            creases.insert(edge_key(m, edge.b));
+        }
+    }
+    if midpoints.is_empty() {
+        return None;
+    }
+
+    let mut uvs = mesh.uvs.clone();
+    let mut uv_midpoints: HashMap<(usize, usize), usize> = HashMap::new();
+    let mut faces = Vec::with_capacity(mesh.faces.len() * 2);
+    for face in mesh.faces.iter() {
+        let v = &face.vertices;
+        let has_uvs = !face.uvs.is_empty();
+        let split: Vec<Option<usize>> = (0..3)
+            .map(|i| midpoints.get(&edge_key(v[i], v[(i + 1) % 3])).copied())
+            .collect();
+        let mut uv_split = [None; 3];
+        if has_uvs {
+            for i in 0..3 {
+                if split[i].is_some() {
+                    let (a, b) = (face.uvs[i], face.uvs[(i + 1) % 3]);
+                    uv_split[i] = Some(*uv_midpoints.entry(edge_key(a, b)).or_insert_with(|| {
+                        uvs.push((0.5 * (uvs[a].0 + uvs[b].0), 0.5 * (uvs[a].1 + uvs[b].1)));
+                        uvs.len() - 1
+                    }));
+                }
+            }
+        }
+
+        // Work out the new triangles as corner lists, where corners 0 to 2 are the old
+        // vertices and 3 to 5 the midpoints of the edges starting at them.
+        let count = split.iter().filter(|s| s.is_some()).count();
+        let pattern: Vec<[usize; 3]> = match count {
+            0 => vec![[0, 1, 2]],
+            3 => vec![[0, 3, 5], [3, 1, 4], [5, 4, 2], [3, 4, 5]],
+            1 => {
+                let i = split.iter().position(|s| s.is_some()).unwrap();
+                vec![[i, 3 + i, (i + 2) % 3], [3 + i, (i + 1) % 3, (i + 2) % 3]]
+            }
+            _ => {
+                // Two edges split: cut off the corner between them, then split the
+                // remaining quad along its shorter diagonal.
+                let i = (split.iter().position(|s| s.is_none()).unwrap() + 1) % 3;
+                let (a, b, c) = (i, (i + 1) % 3, (i + 2) % 3);
+                let (ab, bc) = (3 + a, 3 + b);
+                let corner = |k: usize| {
+                    if k < 3 {
+                        mesh.positions[v[k]]
+                    } else {
+                        positions[split[k - 3].unwrap()]
+                    }
+                };
+                if (corner(bc) - corner(a)).length() < (corner(c) - corner(ab)).length() {
+                    vec![[ab, b, bc], [a, ab, bc], [a, bc, c]]
+                } else {
+                    vec![[ab, b, bc], [a, ab, c], [ab, bc, c]]
+                }
+            }
+        };
+
+        for corners in pattern {
+            let vertex = |k: usize| if k < 3 { v[k] } else { split[k - 3].unwrap() };
+            let vertices = corners.iter().map(|&k| vertex(k)).collect();
+            if has_uvs {
+                let uv = |k: usize| {
+                    if k < 3 {
+                        face.uvs[k]
+                    } else {
+                        uv_split[k - 3].unwrap()
+                    }
+                };
+                faces.push(Face::with_uvs(
+                    vertices,
+                    corners.iter().map(|&k| uv(k)).collect(),
+                ));
+            } else {
+                faces.push(Face::new(vertices));
+            }
+        }
+    }
+
+    let mut split = Mesh::new(positions, uvs, faces);
+    split.creases = creases;
+    return Some(split);
+}
+
+pub fn displace(mesh: &Mesh, height: &dyn Texture, scale: f64) -> Mesh {
+    // Moves each vertex along its normal, averaged over all the faces around it so that
+    // vertices on a crease move as one and the surface stays closed. A vertex on a texture
+    // seam takes its coordinates from the first face that uses it.
+    let nv = mesh.positions.len();
+    let mut normals = vec![Vec3::blank(); nv];
+    let mut vertex_uvs = vec![None; nv];
+    for face in mesh.faces.iter() {
+        let k = face.vertices.len();
+        let mut normal = Vec3::blank();
+        for i in 0..k {
+            let p = mesh.positions[face.vertices[i]];
+            let q = mesh.positions[face.vertices[(i + 1) % k]];
+            normal += Vec3::cross(p, q);
+        }
+        for (i, &v) in face.vertices.iter().enumerate() {
+            normals[v] += normal;
+            if vertex_uvs[v].is_none() && !face.uvs.is_empty() {
+                vertex_uvs[v] = Some(mesh.uvs[face.uvs[i]]);
+            }
+        }
+    }
+
+    let mut displaced = mesh.clone();
+    for v in 0..nv {
+        if normals[v].near_zero() {
+            continue;
+        }
+        let p = mesh.positions[v];
+        let (u, w) = vertex_uvs[v].unwrap_or((0.0, 0.0));
+        let offset = scale * (height.value(u, w, &p).x() - 0.5);
+        displaced.positions[v] = p + offset * Vec3::unit_vector(normals[v]);
+    }
+    return displaced;
+}
@@ -0,0 +1,262 @@
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::hittables::hittable::{HitRecord, Hittable};
+use crate::hittables::triangle::intersect_triangle;
+use crate::material::material::Material;
+use crate::math::aabb::AABB;
+use crate::math::interval::Interval;
+use crate::math::ray::Ray;
+use crate::math::vec3::{Point3, Vec3};
+use crate::texture::image::{ColorSpace, Image};
+
+pub struct Heightfield {
+    heights: Vec<f64>,  // World space heights, row by row along z
+    normals: Vec<Vec3>, // Per-vertex normals, interpolated across each triangle
+    nx: usize,
+    nz: usize,
+    corner: Point3, // The grid vertex at the minimum x and z
+    cell_x: f64,
+    cell_z: f64,
+    mat: Arc<dyn Material>,
+    bbox: AABB,
+}
+
+impl Heightfield {
+    pub fn new(
+        heights: &[f64],
+        nx: usize,
+        nz: usize,
+        corner: Point3,
+        size: Vec3,
+        mat: Arc<dyn Material>,
+    ) -> Heightfield {
+        // Lays an `nx` by `nz` grid of samples (row by row along z) over `size.x()` by
+        // `size.z()` starting at `corner`. Samples are scaled by `size.y()` and raised by
+        // `corner.y()`, so samples in [0,1] span the full box.
+        assert!(nx >= 2 && nz >= 2, "heightfield needs at least 2x2 samples");
+        assert_eq!(heights.len(), nx * nz, "heightfield sample count mismatch");
+
+        let heights: Vec<f64> = heights.iter().map(|h| corner.y() + h * size.y()).collect();
+        let cell_x = size.x() / (nx - 1) as f64;
+        let cell_z = size.z() / (nz - 1) as f64;
+
+        let mut min_height = f64::INFINITY;
+        let mut max_height = f64::NEG_INFINITY;
+        for h in heights.iter() {
+            min_height = min_height.min(*h);
+            max_height = max_height.max(*h);
+        }
+
+        let mut field = Heightfield {
+            heights,
+            normals: Vec::new(),
+            nx,
+            nz,
+            corner,
+            cell_x,
+            cell_z,
+            mat,
+            bbox: AABB::new(
+                Interval::new(corner.x(), corner.x() + size.x()),
+                Interval::new(min_height, max_height),
+                Interval::new(corner.z(), corner.z() + size.z()),
+            ),
+        };
+        field.normals = field.vertex_normals();
+        field
+    }
+
+    #[allow(dead_code)]
+    pub fn from_image(
+        path: &Path,
+        corner: Point3,
+        size: Vec3,
+        mat: Arc<dyn Material>,
+    ) -> io::Result<Heightfield> {
+        // Reads heights from an image, with black at the bottom of the box and white at the
+        // top. Color images are read by their luminance. Image rows run along +z. 16-bit
+        // PNGs keep their full precision, so prefer them for smooth terrain.
+        let image = Image::load(&path.to_string_lossy(), ColorSpace::Linear)?;
+        let mut samples = Vec::with_capacity(image.width() * image.height());
+        for y in 0..image.height() {
+            for x in 0..image.width() {
+                let c = image.pixel_data(x, y);
+                samples.push(0.2126 * c.x() + 0.7152 * c.y() + 0.0722 * c.z());
+            }
+        }
+        return Ok(Heightfield::new(
+            &samples,
+            image.width(),
+            image.height(),
+            corner,
+            size,
+            mat,
+        ));
+    }
+
+    fn height(&self, ix: usize, iz: usize) -> f64 {
+        self.heights[iz * self.nx + ix]
+    }
+
+    fn vertex(&self, ix: usize, iz: usize) -> Point3 {
+        Point3::new(
+            self.corner.x() + ix as f64 * self.cell_x,
+            self.height(ix, iz),
+            self.corner.z() + iz as f64 * self.cell_z,
+        )
+    }
+
+    fn vertex_normals(&self) -> Vec<Vec3> {
+        // Central differences, falling back to one-sided differences along the edges.
+        let mut normals = Vec::with_capacity(self.nx * self.nz);
+        for iz in 0..self.nz {
+            for ix in 0..self.nx {
+                let x0 = ix.saturating_sub(1);
+                let x1 = (ix + 1).min(self.nx - 1);
+                let z0 = iz.saturating_sub(1);
+                let z1 = (iz + 1).min(self.nz - 1);
+                let dhdx =
+                    (self.height(x1, iz) - self.height(x0, iz)) / ((x1 - x0) as f64 * self.cell_x);
+                let dhdz =
+                    (self.height(ix, z1) - self.height(ix, z0)) / ((z1 - z0) as f64 * self.cell_z);
+                normals.push(Vec3::unit_vector(Vec3::new(-dhdx, 1.0, -dhdz)));
+            }
+        }
+        return normals;
+    }
+
+    fn hit_cell(
+        &self,
+        r: &Ray,
+        ray_t: Interval,
+        ix: usize,
+        iz: usize,
+        rec: &mut HitRecord,
+    ) -> bool {
+        // Each cell is split into two triangles along its diagonal.
+        let corners = [(ix, iz), (ix + 1, iz), (ix + 1, iz + 1), (ix, iz + 1)];
+        let mut hit_anything = false;
+        let mut closest_so_far = ray_t.max;
+
+        for tri in [[0, 1, 2], [0, 2, 3]] {
+            let ids = tri.map(|c| corners[c]);
+            let verts = ids.map(|(x, z)| self.vertex(x, z));
+            let hit = match intersect_triangle(r, &verts) {
+                Some(hit) => hit,
+                None => continue,
+            };
+            let (t, b1, b2) = hit;
+            if !Interval::new(ray_t.min, closest_so_far).surrounds(t) {
+                continue;
+            }
+
+            let [n0, n1, n2] = ids.map(|(x, z)| self.normals[z * self.nx + x]);
+            let outward_normal = Vec3::unit_vector((1.0 - b1 - b2) * n0 + b1 * n1 + b2 * n2);
+
+            hit_anything = true;
+            closest_so_far = t;
+            rec.t = t;
+            rec.p = r.at(t);
+            rec.u = (rec.p.x() - self.corner.x()) / ((self.nx - 1) as f64 * self.cell_x);
+            rec.v = (rec.p.z() - self.corner.z()) / ((self.nz - 1) as f64 * self.cell_z);
+            rec.set_face_normal(r, &outward_normal);
+            rec.mat = self.mat.clone();
+        }
+
+        return hit_anything;
+    }
+}
+
+impl Hittable for Heightfield {
+    fn hit(&self, r: &Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        let clipped = match self.bbox.clip(r, &ray_t) {
+            Some(clipped) => clipped,
+            None => return false,
+        };
+
+        // Walk the cells under the ray in order with a 2D DDA over the xz plane, so the first
+        // cell with a hit holds the nearest one.
+        let origin = r.origin();
+        let dir = r.direction();
+        let entry = r.at(clipped.min);
+        let last_x = self.nx - 2;
+        let last_z = self.nz - 2;
+
+        let cell_of = |offset: f64, size: f64, last: usize| -> usize {
+            ((offset / size).floor().max(0.0) as usize).min(last)
+        };
+        let mut ix = cell_of(entry.x() - self.corner.x(), self.cell_x, last_x);
+        let mut iz = cell_of(entry.z() - self.corner.z(), self.cell_z, last_z);
+
+        let step_x: i64 = if dir.x() > 0.0 { 1 } else { -1 };
+        let step_z: i64 = if dir.z() > 0.0 { 1 } else { -1 };
+        let t_delta_x = (self.cell_x / dir.x()).abs();
+        let t_delta_z = (self.cell_z / dir.z()).abs();
+
+        let boundary = |cell: usize, step: i64, start: f64, size: f64| -> f64 {
+            start + (cell as f64 + if step > 0 { 1.0 } else { 0.0 }) * size
+        };
+        let mut t_max_x = if dir.x() != 0.0 {
+            (boundary(ix, step_x, self.corner.x(), self.cell_x) - origin.x()) / dir.x()
+        } else {
+            f64::INFINITY
+        };
+        let mut t_max_z = if dir.z() != 0.0 {
+            (boundary(iz, step_z, self.corner.z(), self.cell_z) - origin.z()) / dir.z()
+        } else {
+            f64::INFINITY
+        };
+
+        let mut t_enter = clipped.min;
+        loop {
+            let t_exit = t_max_x.min(t_max_z).min(clipped.max);
+
+            // Skip cells where the ray passes entirely above or below the cell's corners.
+            let y_enter = origin.y() + t_enter * dir.y();
+            let y_exit = origin.y() + t_exit * dir.y();
+            let cell_heights = [
+                self.height(ix, iz),
+                self.height(ix + 1, iz),
+                self.height(ix, iz + 1),
+                self.height(ix + 1, iz + 1),
+            ];
+            let cell_min = cell_heights.iter().cloned().fold(f64::INFINITY, f64::min);
+            let cell_max = cell_heights
+                .iter()
+                .cloned()
+                .fold(f64::NEG_INFINITY, f64::max);
+            if y_enter.min(y_exit) <= cell_max
+                && y_enter.max(y_exit) >= cell_min
+                && self.hit_cell(r, ray_t, ix, iz, rec)
+            {
+                return true;
+            }
+
+            if t_exit >= clipped.max {
+                return false;
+            }
+
+            if t_max_x < t_max_z {
+                if (step_x < 0 && ix == 0) || (step_x > 0 && ix == last_x) {
+                    return false;
+                }
+                ix = (ix as i64 + step_x) as usize;
+                t_enter = t_max_x;
+                t_max_x += t_delta_x;
+            } else {
+                if (step_z < 0 && iz == 0) || (step_z > 0 && iz == last_z) {
+                    return false;
+                }
+                iz = (iz as i64 + step_z) as usize;
+                t_enter = t_max_z;
+                t_max_z += t_delta_z;
+            }
+        }
+    }
+
+    fn bounding_box(&self) -> &AABB {
+        &self.bbox
+    }
+}
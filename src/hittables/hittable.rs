@@ -12,7 +12,24 @@ pub struct HitRecord {
     pub normal: Vec3,
     pub mat: Arc<dyn Material>,
     pub t: f64,
+    pub u: f64,
+    pub v: f64,
     pub front_face: bool,
+    pub dpdu: Vec3, // How the hit point moves with the texture coordinates, or zero if unknown
+    pub dpdv: Vec3,
+    pub differentials: Option<SurfaceDifferentials>,
+}
+
+// How the hit point and texture coordinates change across the footprint of the ray's
+// differentials, found by `HitRecord::compute_differentials`.
+#[derive(Clone, Copy)]
+pub struct SurfaceDifferentials {
+    pub dpdx: Vec3,
+    pub dpdy: Vec3,
+    pub dudx: f64,
+    pub dvdx: f64,
+    pub dudy: f64,
+    pub dvdy: f64,
 }
 
 impl HitRecord {
@@ -22,7 +39,12 @@ impl HitRecord {
             normal: Vec3::blank(),
             mat: Arc::new(Lambertian::new(Vec3::blank())),
             t: 0.0,
+            u: 0.0,
+            v: 0.0,
             front_face: false,
+            dpdu: Vec3::blank(),
+            dpdv: Vec3::blank(),
+            differentials: None,
         }
     }
 
@@ -35,11 +57,140 @@ impl HitRecord {
         } else {
             self.normal = -outward_normal;
         }
+
+        // Records are reused between objects, so clear the derivatives left by earlier hits.
+        // Shapes that know their derivatives set them after this.
+        self.dpdu = Vec3::blank();
+        self.dpdv = Vec3::blank();
+    }
+
+    pub fn set_medium_scatter(&mut self, r: &Ray, t: f64, phase_function: &Arc<dyn Material>) {
+        // Records a scattering event inside a participating medium at r.at(t). The normal and
+        // face are arbitrary, as phase functions ignore them.
+        self.t = t;
+        self.p = r.at(t);
+        self.normal = Vec3::new(1.0, 0.0, 0.0);
+        self.front_face = true;
+        self.u = 0.0;
+        self.v = 0.0;
+        self.dpdu = Vec3::blank();
+        self.dpdv = Vec3::blank();
+        self.mat = phase_function.clone();
+    }
+
+    pub fn compute_differentials(&mut self, r: &Ray) {
+        // Intersects the ray's differentials with the plane tangent to the surface at the hit,
+        // then finds the texture coordinate changes that best explain the offsets.
+        self.differentials = None;
+        let d = match r.differentials {
+            Some(d) => d,
+            None => return,
+        };
+
+        let n = self.normal;
+        let plane_offset = |origin: &Point3, direction: &Vec3| {
+            let denom = Vec3::dot(&n, direction);
+            if denom.abs() < 1e-12 {
+                return None;
+            }
+            let t = Vec3::dot(&n, &(self.p - *origin)) / denom;
+            return Some(*origin + t * *direction - self.p);
+        };
+        let (dpdx, dpdy) = match (
+            plane_offset(&d.rx_origin, &d.rx_direction),
+            plane_offset(&d.ry_origin, &d.ry_direction),
+        ) {
+            (Some(dpdx), Some(dpdy)) => (dpdx, dpdy),
+            _ => return,
+        };
+
+        // Solve dp = dpdu * du + dpdv * dv in the two axes the surface is least flat along.
+        let (a, b) = if n.x().abs() > n.y().abs() && n.x().abs() > n.z().abs() {
+            (1, 2)
+        } else if n.y().abs() > n.z().abs() {
+            (0, 2)
+        } else {
+            (0, 1)
+        };
+        let det = self.dpdu[a] * self.dpdv[b] - self.dpdv[a] * self.dpdu[b];
+        let solve = |dp: &Vec3| {
+            if det.abs() < 1e-12 {
+                return (0.0, 0.0);
+            }
+            let du = (self.dpdv[b] * dp[a] - self.dpdv[a] * dp[b]) / det;
+            let dv = (self.dpdu[a] * dp[b] - self.dpdu[b] * dp[a]) / det;
+            return (du, dv);
+        };
+        let (dudx, dvdx) = solve(&dpdx);
+        let (dudy, dvdy) = solve(&dpdy);
+
+        self.differentials = Some(SurfaceDifferentials {
+            dpdx,
+            dpdy,
+            dudx,
+            dvdx,
+            dudy,
+            dvdy,
+        });
     }
 }
 
+// A stretch of the ray spent inside a solid, bounded by the surface hits where it enters and
+// exits. An end that lies outside the queried interval may be reported at an infinite `t`.
+#[derive(Clone)]
+pub struct HitSpan {
+    pub enter: HitRecord,
+    pub exit: HitRecord,
+}
+
 pub trait Hittable: Send + Sync {
     fn hit(&self, r: &Ray, ray_t: Interval, rec: &mut HitRecord) -> bool;
 
     fn bounding_box(&self) -> &AABB;
+
+    fn transmittance(&self, r: &Ray, ray_t: Interval) -> f64 {
+        // The fraction of light along `ray_t` that passes through this object unscattered, or
+        // an unbiased estimate of it. The default lets light through only if the ray misses,
+        // which is exact for surfaces. For a volume the chance of a miss is the transmittance
+        // itself, so this stays unbiased, but volumes can override it with less noise.
+        let mut rec = HitRecord::blank();
+        if self.hit(r, ray_t, &mut rec) {
+            return 0.0;
+        }
+        return 1.0;
+    }
+
+    fn hit_spans(&self, r: &Ray, ray_t: Interval, spans: &mut Vec<HitSpan>) {
+        // Appends, in order, every span of `ray_t` that lies inside this object. The default
+        // walks the ray from surface to surface, treating front faces as entries and back
+        // faces as exits, which is correct for any closed surface.
+        let step = 1e-7 / r.direction().length();
+        let mut t_min = ray_t.min;
+        let mut enter: Option<HitRecord> = None;
+        let mut rec = HitRecord::blank();
+
+        while self.hit(r, Interval::new(t_min, ray_t.max), &mut rec) {
+            t_min = rec.t + step * rec.t.abs().max(1.0);
+            if rec.front_face {
+                enter = Some(rec.clone());
+            } else {
+                let enter = enter.take().unwrap_or_else(|| HitRecord {
+                    t: f64::NEG_INFINITY,
+                    ..rec.clone()
+                });
+                spans.push(HitSpan {
+                    enter,
+                    exit: rec.clone(),
+                });
+            }
+        }
+
+        if let Some(enter) = enter {
+            let exit = HitRecord {
+                t: f64::INFINITY,
+                ..enter.clone()
+            };
+            spans.push(HitSpan { enter, exit });
+        }
+    }
 }
@@ -24,6 +24,7 @@ impl HittableList {
         list
     }
 
+    #[allow(dead_code)]
     pub fn clear(&mut self) {
         self.objects.clear();
     }
@@ -50,6 +51,18 @@ impl Hittable for HittableList {
         return hit_anything;
     }
 
+    fn transmittance(&self, r: &Ray, ray_t: Interval) -> f64 {
+        // Light has to get through every object in turn.
+        let mut transmittance = 1.0;
+        for object in self.objects.iter() {
+            transmittance *= object.transmittance(r, ray_t);
+            if transmittance <= 0.0 {
+                break;
+            }
+        }
+        return transmittance;
+    }
+
     fn bounding_box(&self) -> &AABB {
         &self.bbox
     }
@@ -0,0 +1,60 @@
+use std::sync::Arc;
+
+use crate::hittables::hittable::{HitRecord, Hittable};
+use crate::math::aabb::AABB;
+use crate::math::interval::Interval;
+use crate::math::ray::Ray;
+use crate::math::transform::Transform;
+use crate::math::vec3::Vec3;
+
+pub struct Instance {
+    object: Arc<dyn Hittable>,
+    object_to_world: Transform,
+    world_to_object: Transform,
+    bbox: AABB,
+}
+
+impl Instance {
+    pub fn new(object: Arc<dyn Hittable>, object_to_world: Transform) -> Instance {
+        // Places a shared object in the world with an affine transform. Many instances can
+        // wrap the same object, so its geometry is only ever stored once.
+        let bbox = &object_to_world * object.bounding_box();
+        Instance {
+            object,
+            object_to_world,
+            world_to_object: object_to_world.inverse(),
+            bbox,
+        }
+    }
+}
+
+impl Hittable for Instance {
+    fn hit(&self, r: &Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        // Transform the ray from world space to object space.
+        let object_r = &self.world_to_object * r;
+
+        // Determine whether an intersection exists in object space (and if so, where).
+        if !self.object.hit(&object_r, ray_t, rec) {
+            return false;
+        }
+
+        // Transform the intersection from object space back to world space. Transforming the
+        // normal preserves which side of the surface the ray arrived from.
+        rec.p = self.object_to_world.point(&rec.p);
+        rec.normal = Vec3::unit_vector(self.object_to_world.normal(&rec.normal));
+        rec.dpdu = &self.object_to_world * &rec.dpdu;
+        rec.dpdv = &self.object_to_world * &rec.dpdv;
+
+        return true;
+    }
+
+    fn transmittance(&self, r: &Ray, ray_t: Interval) -> f64 {
+        return self
+            .object
+            .transmittance(&(&self.world_to_object * r), ray_t);
+    }
+
+    fn bounding_box(&self) -> &AABB {
+        &self.bbox
+    }
+}
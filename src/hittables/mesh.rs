@@ -0,0 +1,349 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::hittables::bvh::BvhNode;
+use crate::hittables::displacement;
+use crate::hittables::hittable::Hittable;
+use crate::hittables::subdivision;
+use crate::hittables::triangle::Triangle;
+use crate::material::material::Material;
+use crate::math::vec3::{Point3, Vec3};
+use crate::texture::texture::Texture;
+
+#[derive(Clone)]
+pub struct Face {
+    pub vertices: Vec<usize>, // Indices into the mesh positions, counter-clockwise from the front
+    pub uvs: Vec<usize>,      // Indices into the mesh uvs, one per vertex, or empty
+}
+
+impl Face {
+    pub fn new(vertices: Vec<usize>) -> Face {
+        Face {
+            vertices,
+            uvs: Vec::new(),
+        }
+    }
+
+    pub fn with_uvs(vertices: Vec<usize>, uvs: Vec<usize>) -> Face {
+        Face { vertices, uvs }
+    }
+}
+
+// An edge of the mesh along with the faces that share it. Each face is listed with the
+// corner at which the edge starts, going around that face.
+pub struct Edge {
+    pub a: usize,
+    pub b: usize,
+    pub faces: Vec<(usize, usize)>,
+}
+
+// A polygon mesh, kept as an indexed face set so it can be subdivided before being turned
+// into triangles. Texture coordinates are stored per face corner, so seams are preserved.
+#[derive(Clone)]
+pub struct Mesh {
+    pub positions: Vec<Point3>,
+    pub uvs: Vec<(f64, f64)>,
+    pub faces: Vec<Face>,
+    pub creases: HashSet<(usize, usize)>, // Edges kept sharp by subdivision and shading
+}
+
+impl Mesh {
+    pub fn new(positions: Vec<Point3>, uvs: Vec<(f64, f64)>, faces: Vec<Face>) -> Mesh {
+        Mesh {
+            positions,
+            uvs,
+            faces,
+            creases: HashSet::new(),
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn load_obj(path: &Path) -> io::Result<Mesh> {
+        // Reads the vertices, texture coordinates and faces of a Wavefront OBJ file. Normals,
+        // groups and materials are ignored.
+        let text = fs::read_to_string(path)?;
+        return Mesh::parse_obj(&text);
+    }
+
+    pub fn parse_obj(text: &str) -> io::Result<Mesh> {
+        let invalid = |msg: String| io::Error::new(io::ErrorKind::InvalidData, msg);
+
+        let mut positions = Vec::new();
+        let mut uvs = Vec::new();
+        let mut faces = Vec::new();
+
+        for (number, line) in text.lines().enumerate() {
+            let mut fields = line.split_whitespace();
+            let numbers = |fields: std::str::SplitWhitespace| -> io::Result<Vec<f64>> {
+                fields
+                    .map(|f| {
+                        f.parse::<f64>()
+                            .map_err(|_| invalid(format!("line {}: bad number {}", number + 1, f)))
+                    })
+                    .collect()
+            };
+
+            match fields.next() {
+                Some("v") => {
+                    let v = numbers(fields)?;
+                    if v.len() < 3 {
+                        return Err(invalid(format!("line {}: short vertex", number + 1)));
+                    }
+                    positions.push(Point3::new(v[0], v[1], v[2]));
+                }
+                Some("vt") => {
+                    let vt = numbers(fields)?;
+                    if vt.len() < 2 {
+                        return Err(invalid(format!("line {}: short uv", number + 1)));
+                    }
+                    uvs.push((vt[0], vt[1]));
+                }
+                Some("f") => {
+                    // Corners are written v, v/vt, v//vn or v/vt/vn, counting from 1, with
+                    // negative indices counting back from the latest entry.
+                    let mut vertices = Vec::new();
+                    let mut face_uvs = Vec::new();
+                    for corner in fields {
+                        let mut parts = corner.split('/');
+                        let resolve =
+                            |part: Option<&str>, len: usize| -> io::Result<Option<usize>> {
+                                let part = match part {
+                                    Some(part) if !part.is_empty() => part,
+                                    _ => return Ok(None),
+                                };
+                                let index = part.parse::<i64>().map_err(|_| {
+                                    invalid(format!("line {}: bad index {}", number + 1, part))
+                                })?;
+                                let resolved = if index < 0 {
+                                    len as i64 + index
+                                } else {
+                                    index - 1
+                                };
+                                if resolved < 0 || resolved >= len as i64 {
+                                    return Err(invalid(format!(
+                                        "line {}: index {} out of range",
+                                        number + 1,
+                                        part
+                                    )));
+                                }
+                                return Ok(Some(resolved as usize));
+                            };
+                        match resolve(parts.next(), positions.len())? {
+                            Some(v) => vertices.push(v),
+                            None => {
+                                return Err(invalid(format!("line {}: missing vertex", number + 1)))
+                            }
+                        }
+                        if let Some(vt) = resolve(parts.next(), uvs.len())? {
+                            face_uvs.push(vt);
+                        }
+                    }
+                    if vertices.len() < 3 {
+                        return Err(invalid(format!(
+                            "line {}: face with fewer than 3 vertices",
+                            number + 1
+                        )));
+                    }
+                    if face_uvs.len() != vertices.len() {
+                        face_uvs.clear();
+                    }
+                    faces.push(Face::with_uvs(vertices, face_uvs));
+                }
+                _ => {}
+            }
+        }
+
+        return Ok(Mesh::new(positions, uvs, faces));
+    }
+
+    pub fn add_crease(&mut self, a: usize, b: usize) {
+        self.creases.insert(edge_key(a, b));
+    }
+
+    pub fn is_crease(&self, edge: &Edge) -> bool {
+        // Marked edges, boundary edges and edges shared by more than two faces are all sharp.
+        edge.faces.len() != 2 || self.creases.contains(&edge_key(edge.a, edge.b))
+    }
+
+    pub fn edges(&self) -> (Vec<Edge>, HashMap<(usize, usize), usize>) {
+        // Returns the edges in the order they are first met going through the faces, along
+        // with a lookup from each edge's sorted end points to its position in that list.
+        let mut edges: Vec<Edge> = Vec::new();
+        let mut lookup = HashMap::new();
+        for (f, face) in self.faces.iter().enumerate() {
+            let k = face.vertices.len();
+            for i in 0..k {
+                let a = face.vertices[i];
+                let b = face.vertices[(i + 1) % k];
+                let index = *lookup.entry(edge_key(a, b)).or_insert_with(|| {
+                    edges.push(Edge {
+                        a,
+                        b,
+                        faces: Vec::new(),
+                    });
+                    edges.len() - 1
+                });
+                edges[index].faces.push((f, i));
+            }
+        }
+        return (edges, lookup);
+    }
+
+    pub fn subdivide(&self, levels: usize) -> Mesh {
+        // Loop subdivision for all-triangle meshes, Catmull-Clark for anything else. The
+        // first Catmull-Clark step turns every face into quads.
+        let mut mesh = self.clone();
+        for _ in 0..levels {
+            mesh = if mesh.faces.iter().all(|face| face.vertices.len() == 3) {
+                subdivision::loop_subdivide(&mesh)
+            } else {
+                subdivision::catmull_clark(&mesh)
+            };
+        }
+        return mesh;
+    }
+
+    pub fn displace(&self, max_edge: f64, height: Arc<dyn Texture>, scale: f64) -> Mesh {
+        // Splits the faces into triangles no longer than `max_edge` along any side, then
+        // moves every vertex along its normal by `scale` times the height texture there,
+        // read from its first channel, less mid-gray. Finer tessellation gives finer detail,
+        // so subdivide first for a smooth base surface.
+        let tessellated = displacement::tessellate(self, max_edge);
+        displacement::displace(&tessellated, height.as_ref(), scale)
+    }
+
+    pub fn build(&self, mat: Arc<dyn Material>) -> BvhNode {
+        // Splits the faces into triangles with smooth normals and puts them in a BVH.
+        let normals = self.corner_normals();
+
+        let mut triangles: Vec<Arc<dyn Hittable>> = Vec::new();
+        let mut corner = 0;
+        for face in self.faces.iter() {
+            // Points and lines have no area to hit.
+            let k = face.vertices.len();
+            if k < 3 {
+                corner += k;
+                continue;
+            }
+            let uv = |i: usize| {
+                if face.uvs.is_empty() {
+                    (0.0, 0.0)
+                } else {
+                    self.uvs[face.uvs[i]]
+                }
+            };
+
+            // Fan out from the first corner, skipping slivers with no area.
+            for i in 1..k - 1 {
+                let vertices = [
+                    self.positions[face.vertices[0]],
+                    self.positions[face.vertices[i]],
+                    self.positions[face.vertices[i + 1]],
+                ];
+                if Vec3::cross(vertices[1] - vertices[0], vertices[2] - vertices[0]).near_zero() {
+                    continue;
+                }
+                triangles.push(Arc::new(Triangle::new(
+                    vertices,
+                    [
+                        normals[corner],
+                        normals[corner + i],
+                        normals[corner + i + 1],
+                    ],
+                    [uv(0), uv(i), uv(i + 1)],
+                    mat.clone(),
+                )));
+            }
+            corner += k;
+        }
+
+        let l = triangles.len();
+        return BvhNode::new(&mut triangles, 0, l);
+    }
+
+    fn corner_normals(&self) -> Vec<Vec3> {
+        // Gives each face corner the average normal of the faces around its vertex, without
+        // averaging across creases. Corners are grouped by joining those on either side of
+        // every smooth edge.
+        let mut first_corner = Vec::with_capacity(self.faces.len());
+        let mut face_normals = Vec::with_capacity(self.faces.len());
+        let mut corners = 0;
+        for face in self.faces.iter() {
+            first_corner.push(corners);
+            corners += face.vertices.len();
+
+            // Newell's method, which gives the area-weighted normal of any planar polygon.
+            let mut normal = Vec3::blank();
+            let k = face.vertices.len();
+            for i in 0..k {
+                let p = self.positions[face.vertices[i]];
+                let q = self.positions[face.vertices[(i + 1) % k]];
+                normal += Vec3::cross(p, q);
+            }
+            face_normals.push(0.5 * normal);
+        }
+
+        let mut parent: Vec<usize> = (0..corners).collect();
+        let (edges, _) = self.edges();
+        for edge in edges.iter() {
+            if self.is_crease(edge) {
+                continue;
+            }
+            let (f1, i1) = edge.faces[0];
+            let (f2, i2) = edge.faces[1];
+            for vertex in [edge.a, edge.b] {
+                let c1 = first_corner[f1] + self.corner_of(f1, i1, vertex);
+                let c2 = first_corner[f2] + self.corner_of(f2, i2, vertex);
+                let (r1, r2) = (find(&mut parent, c1), find(&mut parent, c2));
+                parent[r1] = r2;
+            }
+        }
+
+        let mut sums = vec![Vec3::blank(); corners];
+        let mut corner_faces = Vec::with_capacity(corners);
+        for (f, face) in self.faces.iter().enumerate() {
+            for _ in 0..face.vertices.len() {
+                let c = corner_faces.len();
+                let root = find(&mut parent, c);
+                sums[root] += face_normals[f];
+                corner_faces.push(f);
+            }
+        }
+
+        let mut normals = Vec::with_capacity(corners);
+        for (c, &f) in corner_faces.iter().enumerate() {
+            let sum = sums[find(&mut parent, c)];
+            if sum.near_zero() {
+                normals.push(Vec3::unit_vector(face_normals[f]));
+            } else {
+                normals.push(Vec3::unit_vector(sum));
+            }
+        }
+        return normals;
+    }
+
+    fn corner_of(&self, f: usize, start: usize, vertex: usize) -> usize {
+        // The corner of face f, on the edge starting at `start`, that holds `vertex`.
+        let face = &self.faces[f];
+        if face.vertices[start] == vertex {
+            start
+        } else {
+            (start + 1) % face.vertices.len()
+        }
+    }
+}
+
+pub fn edge_key(a: usize, b: usize) -> (usize, usize) {
+    (a.min(b), a.max(b))
+}
+
+fn find(parent: &mut [usize], mut c: usize) -> usize {
+    while parent[c] != c {
+        parent[c] = parent[parent[c]];
+        c = parent[c];
+    }
+    return c;
+}
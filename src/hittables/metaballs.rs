@@ -0,0 +1,157 @@
+use std::sync::Arc;
+
+use crate::hittables::hittable::{HitRecord, Hittable};
+use crate::hittables::sdf::sphere_trace;
+use crate::material::material::Material;
+use crate::math::aabb::AABB;
+use crate::math::interval::Interval;
+use crate::math::ray::Ray;
+use crate::math::vec3::{Point3, Vec3};
+
+// Largest slope of the falloff kernel (1 - r²/R²)³ with respect to distance, times R.
+// It peaks at r = R/√5, where the slope is 96 / (25√5 R).
+const KERNEL_SLOPE: f64 = 1.717_300_206_585_39;
+
+#[derive(Clone, Copy)]
+pub struct Metaball {
+    pub center: Point3,
+    pub radius: f64,   // Distance at which the ball's influence falls to zero
+    pub strength: f64, // Field contribution at the center; negative values carve blobs away
+}
+
+impl Metaball {
+    pub fn new(center: Point3, radius: f64, strength: f64) -> Metaball {
+        Metaball {
+            center,
+            radius,
+            strength,
+        }
+    }
+}
+
+pub struct Metaballs {
+    balls: Vec<Metaball>,
+    threshold: f64,
+    mat: Arc<dyn Material>,
+    bbox: AABB,
+    epsilon: f64,
+}
+
+impl Metaballs {
+    pub fn new(balls: Vec<Metaball>, threshold: f64, mat: Arc<dyn Material>) -> Metaballs {
+        // The surface is where the summed field of all the balls equals `threshold`. Each
+        // ball contributes strength * (1 - r²/R²)³ within its radius and nothing beyond.
+        let mut bbox = AABB::EMPTY;
+        for ball in balls.iter() {
+            let rvec = Vec3::new(ball.radius, ball.radius, ball.radius);
+            bbox = AABB::from_boxes(
+                &bbox,
+                &AABB::from_points(ball.center - rvec, ball.center + rvec),
+            );
+        }
+
+        Metaballs {
+            balls,
+            threshold,
+            mat,
+            bbox,
+            epsilon: 1e-5,
+        }
+    }
+
+    fn field(&self, balls: &[&Metaball], p: &Point3) -> f64 {
+        let mut sum = 0.0;
+        for ball in balls {
+            let x = (*p - ball.center).length_squared() / (ball.radius * ball.radius);
+            if x < 1.0 {
+                let k = 1.0 - x;
+                sum += ball.strength * k * k * k;
+            }
+        }
+        return sum - self.threshold;
+    }
+
+    fn outward_normal(&self, p: &Point3) -> Vec3 {
+        // The field falls off away from the blob, so the outward normal is its negated gradient.
+        let mut gradient = Vec3::blank();
+        for ball in self.balls.iter() {
+            let offset = *p - ball.center;
+            let r2 = ball.radius * ball.radius;
+            let x = offset.length_squared() / r2;
+            if x < 1.0 {
+                let k = 1.0 - x;
+                gradient += (-6.0 * ball.strength * k * k / r2) * offset;
+            }
+        }
+        return Vec3::unit_vector(-&gradient);
+    }
+}
+
+impl Hittable for Metaballs {
+    fn hit(&self, r: &Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        let clipped = match self.bbox.clip(r, &ray_t) {
+            Some(clipped) => clipped,
+            None => return false,
+        };
+
+        // Only the balls whose spheres of influence the ray passes through matter along it.
+        // Find them, and the span of the ray inside any of them.
+        let d_len = r.direction().length();
+        let d = r.direction() / d_len;
+        let mut balls = Vec::new();
+        let mut span = Interval::EMPTY;
+        for ball in self.balls.iter() {
+            let oc = ball.center - r.origin();
+            let h = Vec3::dot(&d, &oc);
+            let discriminant = h * h - (oc.length_squared() - ball.radius * ball.radius);
+            if discriminant <= 0.0 {
+                continue;
+            }
+            let sqrtd = discriminant.sqrt();
+            let s0 = (h - sqrtd).max(clipped.min * d_len);
+            let s1 = (h + sqrtd).min(clipped.max * d_len);
+            if s0 < s1 {
+                balls.push(ball);
+                span = Interval::from_intervals(&span, &Interval::new(s0, s1));
+            }
+        }
+        if balls.is_empty() {
+            return false;
+        }
+
+        // The field can change no faster than this per unit of distance, so |field| / lipschitz
+        // bounds the distance to the surface and stepping by it never skips over it.
+        let lipschitz: f64 = balls
+            .iter()
+            .map(|ball| ball.strength.abs() * KERNEL_SLOPE / ball.radius)
+            .sum();
+
+        let s = match sphere_trace(
+            |s| self.field(&balls, &(r.origin() + s * d)) / lipschitz,
+            span,
+            span.min <= ray_t.min * d_len,
+            self.epsilon,
+            1024,
+        ) {
+            Some(s) => s,
+            None => return false,
+        };
+        let t = s / d_len;
+        if !ray_t.surrounds(t) {
+            return false;
+        }
+
+        rec.t = t;
+        rec.p = r.at(t);
+        let outward_normal = self.outward_normal(&rec.p);
+        rec.set_face_normal(r, &outward_normal);
+        rec.u = 0.0;
+        rec.v = 0.0;
+        rec.mat = self.mat.clone();
+        true
+    }
+
+    fn bounding_box(&self) -> &AABB {
+        &self.bbox
+    }
+}
@@ -1,4 +1,25 @@
+pub mod bezier_patch;
 pub mod bvh;
+pub mod cone;
+pub mod constant_medium;
+pub mod csg;
+pub mod cuboid;
+pub mod curve;
+pub mod cylinder;
+pub mod disk;
+pub mod displacement;
+pub mod grid_volume;
+pub mod heightfield;
 pub mod hittable;
 pub mod hittable_list;
+pub mod instance;
+pub mod mesh;
+pub mod metaballs;
+pub mod plane;
+pub mod point_cloud;
+pub mod sdf;
 pub mod sphere;
+pub mod subdivision;
+pub mod torus;
+pub mod triangle;
+pub mod voxel_grid;
@@ -0,0 +1,75 @@
+use std::sync::Arc;
+
+use crate::hittables::hittable::{HitRecord, Hittable};
+use crate::material::material::Material;
+use crate::math::aabb::AABB;
+use crate::math::interval::Interval;
+use crate::math::onb::Onb;
+use crate::math::ray::Ray;
+use crate::math::vec3::{Point3, Vec3};
+
+pub struct Plane {
+    point: Point3,
+    normal: Vec3,
+    uvw: Onb,
+    mat: Arc<dyn Material>,
+    bbox: AABB,
+}
+
+impl Plane {
+    pub fn new(point: Point3, normal: Vec3, mat: Arc<dyn Material>) -> Plane {
+        let normal = Vec3::unit_vector(normal);
+
+        // An infinite plane only has a finite extent along an axis it is perpendicular to.
+        // Every other axis is unbounded, which `BvhNode` keeps out of its hierarchy.
+        let mut axes = [Interval::UNIVERSE; 3];
+        for (axis, interval) in axes.iter_mut().enumerate() {
+            if normal[axis].abs() == 1.0 {
+                *interval = Interval::new(point[axis], point[axis]);
+            }
+        }
+
+        Plane {
+            point,
+            normal,
+            uvw: Onb::new(&normal),
+            mat,
+            bbox: AABB::new(axes[0], axes[1], axes[2]),
+        }
+    }
+}
+
+impl Hittable for Plane {
+    fn hit(&self, r: &Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        let denom = Vec3::dot(&self.normal, &r.direction());
+
+        // No hit if the ray is parallel to the plane.
+        if denom.abs() < 1e-8 {
+            return false;
+        }
+
+        let t = Vec3::dot(&self.normal, &(self.point - r.origin())) / denom;
+        if !ray_t.surrounds(t) {
+            return false;
+        }
+
+        rec.t = t;
+        rec.p = r.at(t);
+
+        // Tile the texture space once per world unit along the plane's tangent axes.
+        let planar = self.uvw.to_local(&(rec.p - self.point));
+        rec.u = planar.x().rem_euclid(1.0);
+        rec.v = planar.y().rem_euclid(1.0);
+
+        rec.set_face_normal(r, &self.normal);
+        rec.dpdu = self.uvw.transform(&Vec3::new(1.0, 0.0, 0.0));
+        rec.dpdv = self.uvw.transform(&Vec3::new(0.0, 1.0, 0.0));
+        rec.mat = self.mat.clone();
+
+        return true;
+    }
+
+    fn bounding_box(&self) -> &AABB {
+        &self.bbox
+    }
+}
@@ -0,0 +1,299 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::color::Color;
+use crate::hittables::disk::disk_extent;
+use crate::hittables::hittable::{HitRecord, Hittable};
+use crate::material::material::Material;
+use crate::math::aabb::AABB;
+use crate::math::interval::Interval;
+use crate::math::ray::Ray;
+use crate::math::vec3::{Point3, Vec3};
+use crate::texture::texture::Texture;
+
+// Points per leaf of the internal hierarchy.
+const LEAF_SIZE: usize = 4;
+
+// Nodes a traversal can have waiting. Each level of the hierarchy halves the points, so this
+// is far more than any cloud needs.
+const MAX_DEPTH: usize = 64;
+
+#[derive(Clone, Copy)]
+pub enum PointShape {
+    Disk,   // A disk facing along the point's normal, like a surfel from a scan
+    Sphere, // A sphere, for points that come without normals
+}
+
+#[derive(Clone, Copy)]
+pub struct CloudPoint {
+    pub position: Point3,
+    pub normal: Vec3,
+    pub color: Color,
+}
+
+// A node of the flattened hierarchy. The left child of an interior node directly follows
+// it, and `index` holds the right child. A leaf holds the `count` points from `index` on.
+struct Node {
+    bbox: AABB,
+    index: usize,
+    count: usize,
+}
+
+// A large set of small points, each a disk or sphere of one shared material. The points are
+// kept in one flat array with a compact BVH of their own rather than as separate hittables,
+// so millions of them fit in memory and the scene sees a single box. A hit's u gives the
+// point's place in the order the points were given, which PointColors turns back into the
+// point's own color.
+pub struct PointCloud {
+    points: Vec<(CloudPoint, u32)>, // Each point with its place in the given order
+    nodes: Vec<Node>,
+    radius: f64,
+    shape: PointShape,
+    mat: Arc<dyn Material>,
+}
+
+impl PointCloud {
+    pub fn new(
+        points: Vec<CloudPoint>,
+        radius: f64,
+        shape: PointShape,
+        mat: Arc<dyn Material>,
+    ) -> PointCloud {
+        let points = points
+            .into_iter()
+            .enumerate()
+            .map(|(id, mut point)| {
+                if !point.normal.near_zero() {
+                    point.normal = Vec3::unit_vector(point.normal);
+                }
+                (point, id as u32)
+            })
+            .collect();
+
+        let mut cloud = PointCloud {
+            points,
+            nodes: Vec::new(),
+            radius,
+            shape,
+            mat,
+        };
+        if cloud.points.is_empty() {
+            cloud.nodes.push(Node {
+                bbox: AABB::EMPTY,
+                index: 0,
+                count: 0,
+            });
+        } else {
+            let mut nodes = Vec::with_capacity(2 * cloud.points.len() / LEAF_SIZE + 1);
+            let len = cloud.points.len();
+            cloud.build(&mut nodes, 0, len);
+            cloud.nodes = nodes;
+        }
+        return cloud;
+    }
+
+    #[allow(dead_code)]
+    pub fn load_xyz(path: &Path) -> io::Result<Vec<CloudPoint>> {
+        // Reads an ASCII point file with one point per line: "x y z", optionally followed by
+        // "r g b" (0 to 255, or 0 to 1 if none is above 1) and then "nx ny nz". Lines
+        // starting with '#' or '//' are skipped.
+        let text = fs::read_to_string(path)?;
+        let invalid = |msg: String| io::Error::new(io::ErrorKind::InvalidData, msg);
+
+        let mut points = Vec::new();
+        let mut max_channel: f64 = 0.0;
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || line.starts_with("//") {
+                continue;
+            }
+            let values = line
+                .split(|c: char| c.is_whitespace() || c == ',')
+                .filter(|field| !field.is_empty())
+                .map(|field| field.parse::<f64>())
+                .collect::<Result<Vec<f64>, _>>()
+                .map_err(|_| invalid(format!("line {}: bad number", number + 1)))?;
+            if values.len() < 3 {
+                return Err(invalid(format!("line {}: expected x y z", number + 1)));
+            }
+
+            let color = if values.len() >= 6 {
+                max_channel = max_channel.max(values[3]).max(values[4]).max(values[5]);
+                Color::new(values[3], values[4], values[5])
+            } else {
+                Color::new(1.0, 1.0, 1.0)
+            };
+            let normal = if values.len() >= 9 {
+                Vec3::new(values[6], values[7], values[8])
+            } else {
+                Vec3::blank()
+            };
+            points.push(CloudPoint {
+                position: Point3::new(values[0], values[1], values[2]),
+                normal,
+                color,
+            });
+        }
+
+        if max_channel > 1.0 {
+            for point in points.iter_mut() {
+                point.color /= 255.0;
+            }
+        }
+        return Ok(points);
+    }
+
+    fn point_box(&self, point: &CloudPoint) -> AABB {
+        let extent = match self.shape {
+            PointShape::Disk if !point.normal.near_zero() => {
+                disk_extent(&point.normal, self.radius)
+            }
+            _ => Vec3::new(self.radius, self.radius, self.radius),
+        };
+        return AABB::from_points(point.position - extent, point.position + extent);
+    }
+
+    fn build(&mut self, nodes: &mut Vec<Node>, start: usize, end: usize) {
+        let mut bbox = AABB::EMPTY;
+        for (point, _) in &self.points[start..end] {
+            bbox = AABB::from_boxes(&bbox, &self.point_box(point));
+        }
+
+        let node = nodes.len();
+        nodes.push(Node {
+            bbox,
+            index: start,
+            count: end - start,
+        });
+        if end - start <= LEAF_SIZE {
+            return;
+        }
+
+        // Split at the median point along the longest axis of the box.
+        let axis = bbox.longest_axis() as usize;
+        let mid = start + (end - start) / 2;
+        self.points[start..end].select_nth_unstable_by(mid - start, |a, b| {
+            a.0.position[axis].total_cmp(&b.0.position[axis])
+        });
+
+        self.build(nodes, start, mid);
+        let right = nodes.len();
+        self.build(nodes, mid, end);
+        nodes[node].index = right;
+        nodes[node].count = 0;
+    }
+
+    fn hit_point(&self, point: &CloudPoint, r: &Ray, ray_t: &Interval) -> Option<(f64, Vec3)> {
+        // Returns the ray parameter of the hit and the outward normal there.
+        match self.shape {
+            PointShape::Disk if !point.normal.near_zero() => {
+                let denom = Vec3::dot(&point.normal, &r.direction());
+                if denom.abs() < 1e-8 {
+                    return None;
+                }
+                let t = Vec3::dot(&point.normal, &(point.position - r.origin())) / denom;
+                if !ray_t.surrounds(t)
+                    || (r.at(t) - point.position).length_squared() > self.radius * self.radius
+                {
+                    return None;
+                }
+                return Some((t, point.normal));
+            }
+            _ => {
+                let oc = point.position - r.origin();
+                let a = r.direction().length_squared();
+                let h = Vec3::dot(&r.direction(), &oc);
+                let c = oc.length_squared() - self.radius * self.radius;
+                let discriminant = h * h - a * c;
+                if discriminant < 0.0 {
+                    return None;
+                }
+                let sqrtd = discriminant.sqrt();
+                let mut t = (h - sqrtd) / a;
+                if !ray_t.surrounds(t) {
+                    t = (h + sqrtd) / a;
+                    if !ray_t.surrounds(t) {
+                        return None;
+                    }
+                }
+                return Some((t, (r.at(t) - point.position) / self.radius));
+            }
+        }
+    }
+}
+
+impl Hittable for PointCloud {
+    fn hit(&self, r: &Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        let mut closest = ray_t.max;
+        let mut found: Option<(usize, f64, Vec3)> = None;
+
+        let mut stack = [0; MAX_DEPTH];
+        let mut depth = 1;
+        while depth > 0 {
+            depth -= 1;
+            let index = stack[depth];
+            let node = &self.nodes[index];
+            if !node.bbox.hit(r, &Interval::new(ray_t.min, closest)) {
+                continue;
+            }
+            if node.count == 0 {
+                stack[depth] = node.index;
+                stack[depth + 1] = index + 1;
+                depth += 2;
+                continue;
+            }
+            for i in node.index..node.index + node.count {
+                if let Some((t, normal)) =
+                    self.hit_point(&self.points[i].0, r, &Interval::new(ray_t.min, closest))
+                {
+                    closest = t;
+                    found = Some((i, t, normal));
+                }
+            }
+        }
+
+        let (i, t, outward_normal) = match found {
+            Some(hit) => hit,
+            None => return false,
+        };
+
+        rec.t = t;
+        rec.p = r.at(t);
+        rec.u = (self.points[i].1 as f64 + 0.5) / self.points.len() as f64;
+        rec.v = 0.0;
+        rec.set_face_normal(r, &outward_normal);
+        rec.mat = self.mat.clone();
+
+        return true;
+    }
+
+    fn bounding_box(&self) -> &AABB {
+        &self.nodes[0].bbox
+    }
+}
+
+// The colors of a cloud's points, looked up by the place in the given order that the cloud
+// passes through u.
+pub struct PointColors {
+    colors: Vec<Color>,
+}
+
+impl PointColors {
+    pub fn new(points: &[CloudPoint]) -> PointColors {
+        PointColors {
+            colors: points.iter().map(|point| point.color).collect(),
+        }
+    }
+}
+
+impl Texture for PointColors {
+    fn value(&self, u: f64, _v: f64, _p: &Point3) -> Color {
+        if self.colors.is_empty() {
+            return Color::new(1.0, 1.0, 1.0);
+        }
+        let index = (u * self.colors.len() as f64) as usize;
+        return self.colors[index.min(self.colors.len() - 1)];
+    }
+}
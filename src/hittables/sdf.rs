@@ -0,0 +1,196 @@
+use std::sync::Arc;
+
+use crate::hittables::hittable::{HitRecord, Hittable};
+use crate::material::material::Material;
+use crate::math::aabb::AABB;
+use crate::math::interval::Interval;
+use crate::math::ray::Ray;
+use crate::math::vec3::{Point3, Vec3};
+
+pub type DistanceFn = dyn Fn(&Point3) -> f64 + Send + Sync;
+
+pub struct SdfHittable {
+    distance: Arc<DistanceFn>,
+    mat: Arc<dyn Material>,
+    bbox: AABB,
+    epsilon: f64,   // Distance at which the march counts as touching the surface
+    max_steps: i64, // March steps before giving up on a ray
+}
+
+impl SdfHittable {
+    pub fn new(distance: Arc<DistanceFn>, bbox: AABB, mat: Arc<dyn Material>) -> SdfHittable {
+        SdfHittable::new_with_precision(distance, bbox, mat, 1e-4, 256)
+    }
+
+    pub fn new_with_precision(
+        distance: Arc<DistanceFn>,
+        bbox: AABB,
+        mat: Arc<dyn Material>,
+        epsilon: f64,
+        max_steps: i64,
+    ) -> SdfHittable {
+        // `distance` must never overestimate the distance to the surface, and the surface must
+        // lie entirely within `bbox`, since rays are only marched inside it.
+        SdfHittable {
+            distance,
+            mat,
+            bbox,
+            epsilon,
+            max_steps,
+        }
+    }
+
+    fn normal(&self, p: &Point3) -> Vec3 {
+        // Estimate the gradient from four samples on a tetrahedron around p.
+        let h = self.epsilon;
+        let mut gradient = Vec3::blank();
+        for k in [
+            Vec3::new(1.0, -1.0, -1.0),
+            Vec3::new(-1.0, -1.0, 1.0),
+            Vec3::new(-1.0, 1.0, -1.0),
+            Vec3::new(1.0, 1.0, 1.0),
+        ] {
+            gradient += (self.distance)(&(*p + h * k)) * k;
+        }
+        return Vec3::unit_vector(gradient);
+    }
+}
+
+impl Hittable for SdfHittable {
+    fn hit(&self, r: &Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        let clipped = match self.bbox.clip(r, &ray_t) {
+            Some(clipped) => clipped,
+            None => return false,
+        };
+
+        // March along the unit direction so that steps are measured in world units.
+        let d_len = r.direction().length();
+        let d = r.direction() / d_len;
+        let s = match sphere_trace(
+            |s| (self.distance)(&(r.origin() + s * d)),
+            Interval::new(clipped.min * d_len, clipped.max * d_len),
+            clipped.min <= ray_t.min,
+            self.epsilon,
+            self.max_steps,
+        ) {
+            Some(s) => s,
+            None => return false,
+        };
+        let t = s / d_len;
+        if !ray_t.surrounds(t) {
+            return false;
+        }
+
+        rec.t = t;
+        rec.p = r.at(t);
+        let outward_normal = self.normal(&rec.p);
+        rec.set_face_normal(r, &outward_normal);
+        rec.u = 0.0;
+        rec.v = 0.0;
+        rec.mat = self.mat.clone();
+        true
+    }
+
+    fn bounding_box(&self) -> &AABB {
+        &self.bbox
+    }
+}
+
+pub fn sphere_trace(
+    distance: impl Fn(f64) -> f64,
+    span: Interval,
+    at_ray_start: bool,
+    epsilon: f64,
+    max_steps: i64,
+) -> Option<f64> {
+    // Marches along a unit-speed ray over `span`, where `distance(s)` is a signed bound on
+    // the distance from the point at s to the surface, and returns the first s within
+    // epsilon of it. Either sign may be inside.
+
+    // A ray that starts on its own origin may be leaving the surface, within epsilon of it.
+    // Step out of that band first, then note which side of the surface the ray is on.
+    let mut s = span.min;
+    let mut dist = distance(s);
+    let mut steps = 0;
+    if at_ray_start {
+        while dist.abs() < epsilon && steps < max_steps && s < span.max {
+            s += epsilon;
+            dist = distance(s);
+            steps += 1;
+        }
+    }
+    let side = if dist < 0.0 { -1.0 } else { 1.0 };
+
+    while steps < max_steps && s < span.max {
+        let signed = side * dist;
+        if signed < epsilon {
+            return Some(s);
+        }
+        s += signed;
+        dist = distance(s);
+        steps += 1;
+    }
+    None
+}
+
+// Distance functions to build shapes from. Each returns the signed distance from p to the
+// surface, negative inside.
+
+pub fn sphere(p: &Point3, radius: f64) -> f64 {
+    p.length() - radius
+}
+
+pub fn round_box(p: &Point3, half_extents: &Vec3, radius: f64) -> f64 {
+    let q = Vec3::new(
+        p.x().abs() - half_extents.x() + radius,
+        p.y().abs() - half_extents.y() + radius,
+        p.z().abs() - half_extents.z() + radius,
+    );
+    let outside = Vec3::new(q.x().max(0.0), q.y().max(0.0), q.z().max(0.0)).length();
+    let inside = q.x().max(q.y()).max(q.z()).min(0.0);
+    return outside + inside - radius;
+}
+
+pub fn torus(p: &Point3, major_radius: f64, minor_radius: f64) -> f64 {
+    // A torus around the y axis.
+    let ring = (p.x() * p.x() + p.z() * p.z()).sqrt() - major_radius;
+    return (ring * ring + p.y() * p.y()).sqrt() - minor_radius;
+}
+
+pub fn mandelbulb(p: &Point3, power: f64, iterations: i64) -> f64 {
+    // Distance estimate for the Mandelbulb fractal, which fits inside a radius of about 1.2.
+    let mut z = *p;
+    let mut dr = 1.0;
+    let mut r = 0.0;
+
+    for _ in 0..iterations {
+        r = z.length();
+        if r > 2.0 || r == 0.0 {
+            break;
+        }
+
+        // Convert to polar coordinates, then scale and rotate the point.
+        let theta = (z.z() / r).acos() * power;
+        let phi = f64::atan2(z.y(), z.x()) * power;
+        dr = r.powf(power - 1.0) * power * dr + 1.0;
+
+        let zr = r.powf(power);
+        z =
+            zr * Vec3::new(
+                theta.sin() * phi.cos(),
+                phi.sin() * theta.sin(),
+                theta.cos(),
+            ) + *p;
+    }
+
+    if r == 0.0 {
+        return 0.0;
+    }
+    return 0.5 * r.ln() * r / dr;
+}
+
+pub fn smooth_union(d1: f64, d2: f64, k: f64) -> f64 {
+    // Blends two distances together over a region of width k.
+    let h = (0.5 + 0.5 * (d2 - d1) / k).clamp(0.0, 1.0);
+    return d2 * (1.0 - h) + d1 * h - k * h * (1.0 - h);
+}
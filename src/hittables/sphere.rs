@@ -1,9 +1,10 @@
-use crate::hittables::hittable::{HitRecord, Hittable};
+use crate::hittables::hittable::{HitRecord, HitSpan, Hittable};
 use crate::material::material::Material;
 use crate::math::aabb::AABB;
 use crate::math::interval::Interval;
 use crate::math::ray::Ray;
 use crate::math::vec3::{Point3, Vec3};
+use crate::util;
 use std::sync::Arc;
 
 pub struct Sphere {
@@ -18,8 +19,8 @@ impl Sphere {
         let rvec = Vec3::new(radius, radius, radius);
         Sphere {
             center: Ray::new(static_center, Vec3::blank()),
-            radius: radius,
-            mat: mat,
+            radius,
+            mat,
             bbox: AABB::from_points(static_center - rvec, static_center + rvec),
         }
     }
@@ -41,10 +42,9 @@ impl Sphere {
             bbox: AABB::from_boxes(&box1, &box2),
         }
     }
-}
 
-impl Hittable for Sphere {
-    fn hit(&self, r: &Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+    fn roots(&self, r: &Ray) -> Option<(f64, f64)> {
+        // Returns the ray parameters where the ray enters and leaves the sphere, if it does.
         let current_center = self.center.at(r.time());
         let oc = current_center - r.origin();
         let a = r.direction().length_squared();
@@ -53,25 +53,68 @@ impl Hittable for Sphere {
 
         let discriminant = h * h - a * c;
         if discriminant < 0.0 {
-            return false;
+            return None;
         }
 
         let sqrtd = discriminant.sqrt();
+        return Some(((h - sqrtd) / a, (h + sqrtd) / a));
+    }
+
+    fn fill_record(&self, r: &Ray, root: f64, rec: &mut HitRecord) {
+        let current_center = self.center.at(r.time());
+        rec.t = root;
+        rec.p = r.at(rec.t);
+        let outward_normal = (rec.p - current_center) / self.radius;
+        rec.set_face_normal(r, &outward_normal);
+        (rec.u, rec.v) = Sphere::get_sphere_uv(&outward_normal);
+        (rec.dpdu, rec.dpdv) = Sphere::get_sphere_derivatives(&(rec.p - current_center));
+        rec.mat = self.mat.clone();
+    }
+
+    fn get_sphere_derivatives(p: &Vec3) -> (Vec3, Vec3) {
+        // p: the hit point relative to the center. Differentiates the mapping of
+        // get_sphere_uv, where u turns about the Y axis and v climbs from pole to pole.
+        // Both derivatives vanish at the poles, where the mapping pinches to a point.
+        let dpdu = 2.0 * util::PI * Vec3::new(p.z(), 0.0, -p.x());
+        let rho = (p.x() * p.x() + p.z() * p.z()).sqrt();
+        if rho < 1e-12 {
+            return (dpdu, Vec3::blank());
+        }
+        let dpdv = util::PI * Vec3::new(-p.x() * p.y() / rho, rho, -p.z() * p.y() / rho);
+        return (dpdu, dpdv);
+    }
+
+    fn get_sphere_uv(p: &Point3) -> (f64, f64) {
+        // p: a given point on the sphere of radius one, centered at the origin.
+        // u: returned value [0,1] of angle around the Y axis from X=-1.
+        // v: returned value [0,1] of angle from Y=-1 to Y=+1.
+        //     <1 0 0> yields <0.50 0.50>       <-1  0  0> yields <0.00 0.50>
+        //     <0 1 0> yields <0.50 1.00>       < 0 -1  0> yields <0.50 0.00>
+        //     <0 0 1> yields <0.25 0.50>       < 0  0 -1> yields <0.75 0.50>
+        let theta = (-p.y()).acos();
+        let phi = f64::atan2(-p.z(), p.x()) + util::PI;
+
+        return (phi / (2.0 * util::PI), theta / util::PI);
+    }
+}
+
+impl Hittable for Sphere {
+    fn hit(&self, r: &Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        let (near, far) = match self.roots(r) {
+            Some(roots) => roots,
+            None => return false,
+        };
 
         // Find the nearest root that lies in the acceptable range.
-        let mut root = (h - sqrtd) / a;
+        let mut root = near;
         if !ray_t.surrounds(root) {
-            root = (h + sqrtd) / a;
+            root = far;
             if !ray_t.surrounds(root) {
                 return false;
             }
         }
 
-        rec.t = root;
-        rec.p = r.at(rec.t);
-        let outward_normal = (rec.p - current_center) / self.radius;
-        rec.set_face_normal(r, &outward_normal);
-        rec.mat = self.mat.clone();
+        self.fill_record(r, root, rec);
 
         return true;
     }
@@ -79,4 +122,21 @@ impl Hittable for Sphere {
     fn bounding_box(&self) -> &AABB {
         &self.bbox
     }
+
+    fn hit_spans(&self, r: &Ray, ray_t: Interval, spans: &mut Vec<HitSpan>) {
+        let (near, far) = match self.roots(r) {
+            Some(roots) => roots,
+            None => return,
+        };
+
+        if far <= ray_t.min || near >= ray_t.max {
+            return;
+        }
+
+        let mut enter = HitRecord::blank();
+        let mut exit = HitRecord::blank();
+        self.fill_record(r, near, &mut enter);
+        self.fill_record(r, far, &mut exit);
+        spans.push(HitSpan { enter, exit });
+    }
 }
@@ -0,0 +1,259 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::hittables::mesh::{edge_key, Edge, Face, Mesh};
+use crate::math::vec3::Point3;
+
+// The neighbourhood of each vertex: every vertex it shares an edge with, and those of them
+// it shares a crease with.
+struct Neighbours {
+    all: Vec<Vec<usize>>,
+    creased: Vec<Vec<usize>>,
+}
+
+impl Neighbours {
+    fn new(mesh: &Mesh, edges: &[Edge]) -> Neighbours {
+        let mut all = vec![Vec::new(); mesh.positions.len()];
+        let mut creased = vec![Vec::new(); mesh.positions.len()];
+        for edge in edges.iter() {
+            all[edge.a].push(edge.b);
+            all[edge.b].push(edge.a);
+            if mesh.is_crease(edge) {
+                creased[edge.a].push(edge.b);
+                creased[edge.b].push(edge.a);
+            }
+        }
+        Neighbours { all, creased }
+    }
+}
+
+// Texture coordinates for the new face corners. Each face interpolates its own corners
+// linearly, and corners that share coordinates share the new ones too, so seams stay put.
+struct UvBuilder {
+    uvs: Vec<(f64, f64)>,
+    midpoints: HashMap<(usize, usize), usize>,
+}
+
+impl UvBuilder {
+    fn new(mesh: &Mesh) -> UvBuilder {
+        UvBuilder {
+            uvs: mesh.uvs.clone(),
+            midpoints: HashMap::new(),
+        }
+    }
+
+    fn midpoint(&mut self, a: usize, b: usize) -> usize {
+        let uvs = &mut self.uvs;
+        return *self.midpoints.entry(edge_key(a, b)).or_insert_with(|| {
+            uvs.push((0.5 * (uvs[a].0 + uvs[b].0), 0.5 * (uvs[a].1 + uvs[b].1)));
+            uvs.len() - 1
+        });
+    }
+
+    fn centroid(&mut self, corners: &[usize]) -> usize {
+        let n = corners.len() as f64;
+        let u = corners.iter().map(|&c| self.uvs[c].0).sum::<f64>() / n;
+        let v = corners.iter().map(|&c| self.uvs[c].1).sum::<f64>() / n;
+        self.uvs.push((u, v));
+        return self.uvs.len() - 1;
+    }
+}
+
+fn split_creases(
+    mesh: &Mesh,
+    lookup: &HashMap<(usize, usize), usize>,
+    first_edge_point: usize,
+) -> HashSet<(usize, usize)> {
+    // A marked crease becomes two creases, meeting at the new point on the edge.
+    let mut creases = HashSet::new();
+    for &(a, b) in mesh.creases.iter() {
+        if let Some(&e) = lookup.get(&(a, b)) {
+            creases.insert(edge_key(a, first_edge_point + e));
+            creases.insert(edge_key(first_edge_point + e, b));
+        }
+    }
+    return creases;
+}
+
+pub fn loop_subdivide(mesh: &Mesh) -> Mesh {
+    // One level of Loop subdivision. New vertices come after the old ones, one per edge in
+    // the order of `Mesh::edges`.
+    let (edges, lookup) = mesh.edges();
+    let neighbours = Neighbours::new(mesh, &edges);
+    let nv = mesh.positions.len();
+
+    let mut positions = Vec::with_capacity(nv + edges.len());
+    for v in 0..nv {
+        let p = mesh.positions[v];
+        let creased = &neighbours.creased[v];
+        let all = &neighbours.all[v];
+        let new_p = if creased.len() == 2 {
+            // Along a crease, only the two crease neighbours pull on the vertex.
+            0.75 * p + 0.125 * (mesh.positions[creased[0]] + mesh.positions[creased[1]])
+        } else if creased.len() > 2 || all.is_empty() {
+            // Corners where several creases meet stay fixed.
+            p
+        } else {
+            let n = all.len() as f64;
+            let beta = if all.len() == 3 {
+                3.0 / 16.0
+            } else {
+                3.0 / (8.0 * n)
+            };
+            let mut sum = Point3::blank();
+            for &u in all.iter() {
+                sum += mesh.positions[u];
+            }
+            (1.0 - n * beta) * p + beta * sum
+        };
+        positions.push(new_p);
+    }
+
+    for edge in edges.iter() {
+        let a = mesh.positions[edge.a];
+        let b = mesh.positions[edge.b];
+        if mesh.is_crease(edge) {
+            positions.push(0.5 * (a + b));
+        } else {
+            // Weigh in the vertices opposite the edge in its two triangles.
+            let opposite =
+                |(f, i): (usize, usize)| mesh.positions[mesh.faces[f].vertices[(i + 2) % 3]];
+            let c = opposite(edge.faces[0]);
+            let d = opposite(edge.faces[1]);
+            positions.push(0.375 * (a + b) + 0.125 * (c + d));
+        }
+    }
+
+    let mut uvs = UvBuilder::new(mesh);
+    let mut faces = Vec::with_capacity(4 * mesh.faces.len());
+    for face in mesh.faces.iter() {
+        let v = &face.vertices;
+        let e = |i: usize| nv + lookup[&edge_key(v[i], v[(i + 1) % 3])];
+        let (ab, bc, ca) = (e(0), e(1), e(2));
+        let new_vertices = [
+            vec![v[0], ab, ca],
+            vec![ab, v[1], bc],
+            vec![ca, bc, v[2]],
+            vec![ab, bc, ca],
+        ];
+
+        if face.uvs.is_empty() {
+            for vertices in new_vertices {
+                faces.push(Face::new(vertices));
+            }
+        } else {
+            let t = &face.uvs;
+            let (tab, tbc, tca) = (
+                uvs.midpoint(t[0], t[1]),
+                uvs.midpoint(t[1], t[2]),
+                uvs.midpoint(t[2], t[0]),
+            );
+            let new_uvs = [
+                vec![t[0], tab, tca],
+                vec![tab, t[1], tbc],
+                vec![tca, tbc, t[2]],
+                vec![tab, tbc, tca],
+            ];
+            for (vertices, corner_uvs) in new_vertices.into_iter().zip(new_uvs) {
+                faces.push(Face::with_uvs(vertices, corner_uvs));
+            }
+        }
+    }
+
+    let mut subdivided = Mesh::new(positions, uvs.uvs, faces);
+    subdivided.creases = split_creases(mesh, &lookup, nv);
+    return subdivided;
+}
+
+pub fn catmull_clark(mesh: &Mesh) -> Mesh {
+    // One level of Catmull-Clark subdivision, which turns a face with k sides into k quads.
+    // New vertices come after the old ones: first one per edge in the order of
+    // `Mesh::edges`, then one per face.
+    let (edges, lookup) = mesh.edges();
+    let neighbours = Neighbours::new(mesh, &edges);
+    let nv = mesh.positions.len();
+    let first_face_point = nv + edges.len();
+
+    let mut face_points = Vec::with_capacity(mesh.faces.len());
+    let mut face_point_sums = vec![Point3::blank(); nv];
+    let mut face_counts = vec![0usize; nv];
+    for face in mesh.faces.iter() {
+        let mut centroid = Point3::blank();
+        for &v in face.vertices.iter() {
+            centroid += mesh.positions[v];
+        }
+        centroid /= face.vertices.len() as f64;
+        for &v in face.vertices.iter() {
+            face_point_sums[v] += centroid;
+            face_counts[v] += 1;
+        }
+        face_points.push(centroid);
+    }
+
+    let mut positions = Vec::with_capacity(first_face_point + mesh.faces.len());
+    for v in 0..nv {
+        let p = mesh.positions[v];
+        let creased = &neighbours.creased[v];
+        let all = &neighbours.all[v];
+        let new_p = if creased.len() == 2 {
+            (mesh.positions[creased[0]] + 6.0 * p + mesh.positions[creased[1]]) / 8.0
+        } else if creased.len() > 2 || all.is_empty() || face_counts[v] == 0 {
+            p
+        } else {
+            // (Q + 2R + (n - 3)P) / n, with Q the average of the surrounding face points
+            // and R the average of the surrounding edge midpoints.
+            let n = all.len() as f64;
+            let q = face_point_sums[v] / face_counts[v] as f64;
+            let mut r = Point3::blank();
+            for &u in all.iter() {
+                r += 0.5 * (p + mesh.positions[u]);
+            }
+            r /= n;
+            (q + 2.0 * r + (n - 3.0) * p) / n
+        };
+        positions.push(new_p);
+    }
+
+    for edge in edges.iter() {
+        let a = mesh.positions[edge.a];
+        let b = mesh.positions[edge.b];
+        if mesh.is_crease(edge) {
+            positions.push(0.5 * (a + b));
+        } else {
+            let f1 = face_points[edge.faces[0].0];
+            let f2 = face_points[edge.faces[1].0];
+            positions.push(0.25 * (a + b + f1 + f2));
+        }
+    }
+
+    positions.extend(face_points);
+
+    let mut uvs = UvBuilder::new(mesh);
+    let mut faces = Vec::new();
+    for (f, face) in mesh.faces.iter().enumerate() {
+        let v = &face.vertices;
+        let k = v.len();
+        let e = |i: usize| nv + lookup[&edge_key(v[i % k], v[(i + 1) % k])];
+        let face_point = first_face_point + f;
+
+        if face.uvs.is_empty() {
+            for (i, &corner) in v.iter().enumerate() {
+                faces.push(Face::new(vec![corner, e(i), face_point, e(i + k - 1)]));
+            }
+        } else {
+            let t = &face.uvs;
+            let centre = uvs.centroid(t);
+            for i in 0..k {
+                let next = uvs.midpoint(t[i], t[(i + 1) % k]);
+                let previous = uvs.midpoint(t[(i + k - 1) % k], t[i]);
+                faces.push(Face::with_uvs(
+                    vec![v[i], e(i), face_point, e(i + k - 1)],
+                    vec![t[i], next, centre, previous],
+                ));
+            }
+        }
+    }
+
+    let mut subdivided = Mesh::new(positions, uvs.uvs, faces);
+    subdivided.creases = split_creases(mesh, &lookup, nv);
+    return subdivided;
+}
@@ -0,0 +1,131 @@
+use std::sync::Arc;
+
+use crate::hittables::hittable::{HitRecord, Hittable};
+use crate::material::material::Material;
+use crate::math::aabb::AABB;
+use crate::math::interval::Interval;
+use crate::math::onb::Onb;
+use crate::math::polynomial;
+use crate::math::ray::Ray;
+use crate::math::vec3::{Point3, Vec3};
+use crate::util;
+
+pub struct Torus {
+    center: Point3,
+    major_radius: f64,
+    minor_radius: f64,
+    uvw: Onb,
+    mat: Arc<dyn Material>,
+    bbox: AABB,
+}
+
+impl Torus {
+    pub fn new(
+        center: Point3,
+        axis: Vec3,
+        major_radius: f64,
+        minor_radius: f64,
+        mat: Arc<dyn Material>,
+    ) -> Torus {
+        // A ring whose tube of `minor_radius` sweeps a circle of `major_radius` around `axis`.
+        let uvw = Onb::new(&axis);
+        let a = uvw.w();
+        let extent = Vec3::new(
+            major_radius * (1.0 - a.x() * a.x()).max(0.0).sqrt() + minor_radius,
+            major_radius * (1.0 - a.y() * a.y()).max(0.0).sqrt() + minor_radius,
+            major_radius * (1.0 - a.z() * a.z()).max(0.0).sqrt() + minor_radius,
+        );
+        Torus {
+            center,
+            major_radius,
+            minor_radius,
+            uvw,
+            mat,
+            bbox: AABB::from_points(center - extent, center + extent),
+        }
+    }
+
+    fn implicit(&self, p: &Point3) -> f64 {
+        let big_r2 = self.major_radius * self.major_radius;
+        let k = p.length_squared() + big_r2 - self.minor_radius * self.minor_radius;
+        return k * k - 4.0 * big_r2 * (p.x() * p.x() + p.y() * p.y());
+    }
+}
+
+impl Hittable for Torus {
+    fn hit(&self, r: &Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        // Work in the torus' frame with a unit direction, where the axis is z.
+        let o = self.uvw.to_local(&(r.origin() - self.center));
+        let d_local = self.uvw.to_local(&r.direction());
+        let d_len = d_local.length();
+        let d = d_local / d_len;
+
+        // Reject against the bounding sphere and restart the ray from its near side. Keeping
+        // the origin close to the torus keeps the quartic's coefficients well conditioned.
+        let bound = self.major_radius + self.minor_radius;
+        let b = Vec3::dot(&o, &d);
+        let c = o.length_squared() - bound * bound;
+        let discriminant = b * b - c;
+        if discriminant < 0.0 {
+            return false;
+        }
+        let shift = (-b - discriminant.sqrt()).max(0.0);
+        let o = o + shift * d;
+
+        let big_r2 = self.major_radius * self.major_radius;
+        let f = Vec3::dot(&o, &d);
+        let k = o.length_squared() + big_r2 - self.minor_radius * self.minor_radius;
+        let coeffs = [
+            k * k - 4.0 * big_r2 * (o.x() * o.x() + o.y() * o.y()),
+            4.0 * f * k - 8.0 * big_r2 * (o.x() * d.x() + o.y() * d.y()),
+            4.0 * f * f + 2.0 * k - 4.0 * big_r2 * (d.x() * d.x() + d.y() * d.y()),
+            4.0 * f,
+            1.0,
+        ];
+
+        let mut roots = polynomial::solve_quartic(coeffs);
+        roots.sort_by(f64::total_cmp);
+
+        for s in roots {
+            // Polish the closed-form root with a Newton step on the implicit surface.
+            let mut s = s;
+            let p = o + s * d;
+            let k = p.length_squared() + big_r2 - self.minor_radius * self.minor_radius;
+            let gradient = 4.0 * k * p - 8.0 * big_r2 * Vec3::new(p.x(), p.y(), 0.0);
+            let slope = Vec3::dot(&gradient, &d);
+            if slope.abs() > 1e-12 {
+                s -= self.implicit(&p) / slope;
+            }
+
+            let t = (shift + s) / d_len;
+            if !ray_t.surrounds(t) {
+                continue;
+            }
+
+            let p = o + s * d;
+            let ring = Vec3::new(p.x(), p.y(), 0.0);
+            let ring_len = ring.length();
+            let tube_center = if ring_len > 0.0 {
+                (self.major_radius / ring_len) * ring
+            } else {
+                Vec3::blank()
+            };
+            let outward_normal = Vec3::unit_vector(p - tube_center);
+
+            rec.t = t;
+            rec.p = r.at(t);
+            rec.u = (f64::atan2(p.y(), p.x()) + util::PI) / (2.0 * util::PI);
+            rec.v = (f64::atan2(p.z(), ring_len - self.major_radius) + util::PI) / (2.0 * util::PI);
+            rec.set_face_normal(r, &self.uvw.transform(&outward_normal));
+            rec.mat = self.mat.clone();
+
+            return true;
+        }
+
+        return false;
+    }
+
+    fn bounding_box(&self) -> &AABB {
+        &self.bbox
+    }
+}
@@ -0,0 +1,130 @@
+use std::sync::Arc;
+
+use crate::hittables::hittable::{HitRecord, Hittable};
+use crate::material::material::Material;
+use crate::math::aabb::AABB;
+use crate::math::interval::Interval;
+use crate::math::ray::Ray;
+use crate::math::vec3::{Point3, Vec3};
+
+// A mesh triangle. Shading normals and texture coordinates are interpolated from the
+// vertices, while the winding of the vertices decides which side is the front face.
+pub struct Triangle {
+    vertices: [Point3; 3],
+    normals: [Vec3; 3],
+    uvs: [(f64, f64); 3],
+    geometric_normal: Vec3,
+    dpdu: Vec3,
+    dpdv: Vec3,
+    mat: Arc<dyn Material>,
+    bbox: AABB,
+}
+
+impl Triangle {
+    pub fn new(
+        vertices: [Point3; 3],
+        normals: [Vec3; 3],
+        uvs: [(f64, f64); 3],
+        mat: Arc<dyn Material>,
+    ) -> Triangle {
+        let geometric_normal = Vec3::unit_vector(Vec3::cross(
+            vertices[1] - vertices[0],
+            vertices[2] - vertices[0],
+        ));
+        // Solve for the derivatives of position with respect to the texture coordinates from
+        // the two edges leaving the first vertex. Without usable texture coordinates, any
+        // pair of directions across the triangle will do.
+        let dp1 = vertices[1] - vertices[0];
+        let dp2 = vertices[2] - vertices[0];
+        let (du1, dv1) = (uvs[1].0 - uvs[0].0, uvs[1].1 - uvs[0].1);
+        let (du2, dv2) = (uvs[2].0 - uvs[0].0, uvs[2].1 - uvs[0].1);
+        let det = du1 * dv2 - dv1 * du2;
+        let (dpdu, dpdv) = if det.abs() < 1e-12 {
+            (dp1, dp2)
+        } else {
+            ((dv2 * dp1 - dv1 * dp2) / det, (du1 * dp2 - du2 * dp1) / det)
+        };
+
+        let bbox = AABB::from_boxes(
+            &AABB::from_points(vertices[0], vertices[1]),
+            &AABB::from_points(vertices[2], vertices[2]),
+        );
+
+        Triangle {
+            vertices,
+            normals,
+            uvs,
+            geometric_normal,
+            dpdu,
+            dpdv,
+            mat,
+            bbox,
+        }
+    }
+}
+
+impl Hittable for Triangle {
+    fn hit(&self, r: &Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        let (t, b1, b2) = match intersect_triangle(r, &self.vertices) {
+            Some(hit) => hit,
+            None => return false,
+        };
+        if !ray_t.surrounds(t) {
+            return false;
+        }
+        let b0 = 1.0 - b1 - b2;
+
+        rec.t = t;
+        rec.p = r.at(t);
+        rec.u = b0 * self.uvs[0].0 + b1 * self.uvs[1].0 + b2 * self.uvs[2].0;
+        rec.v = b0 * self.uvs[0].1 + b1 * self.uvs[1].1 + b2 * self.uvs[2].1;
+
+        // The front face comes from the flat triangle, so that the interpolated normal can't
+        // flip a ray's notion of entering or leaving a closed mesh.
+        let shading_normal =
+            Vec3::unit_vector(b0 * self.normals[0] + b1 * self.normals[1] + b2 * self.normals[2]);
+        rec.front_face = Vec3::dot(&r.direction(), &self.geometric_normal) < 0.0;
+        rec.normal = if rec.front_face {
+            shading_normal
+        } else {
+            -&shading_normal
+        };
+        rec.dpdu = self.dpdu;
+        rec.dpdv = self.dpdv;
+        rec.mat = self.mat.clone();
+
+        return true;
+    }
+
+    fn bounding_box(&self) -> &AABB {
+        &self.bbox
+    }
+}
+
+pub fn intersect_triangle(r: &Ray, verts: &[Point3; 3]) -> Option<(f64, f64, f64)> {
+    // Möller–Trumbore ray/triangle intersection. Returns the ray parameter and the
+    // barycentric weights of the second and third vertices.
+    let edge1 = verts[1] - verts[0];
+    let edge2 = verts[2] - verts[0];
+    let pvec = Vec3::cross(r.direction(), edge2);
+    let det = Vec3::dot(&edge1, &pvec);
+    if det.abs() < 1e-12 {
+        return None;
+    }
+
+    let inv_det = 1.0 / det;
+    let tvec = r.origin() - verts[0];
+    let b1 = Vec3::dot(&tvec, &pvec) * inv_det;
+    if !(0.0..=1.0).contains(&b1) {
+        return None;
+    }
+
+    let qvec = Vec3::cross(tvec, edge1);
+    let b2 = Vec3::dot(&r.direction(), &qvec) * inv_det;
+    if b2 < 0.0 || b1 + b2 > 1.0 {
+        return None;
+    }
+
+    let t = Vec3::dot(&edge2, &qvec) * inv_det;
+    return Some((t, b1, b2));
+}
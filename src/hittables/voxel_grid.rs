@@ -0,0 +1,210 @@
+use std::sync::Arc;
+
+use crate::hittables::hittable::{HitRecord, Hittable};
+use crate::material::material::Material;
+use crate::math::aabb::AABB;
+use crate::math::interval::Interval;
+use crate::math::ray::Ray;
+use crate::math::vec3::{Point3, Vec3};
+
+pub const EMPTY_VOXEL: u8 = 0;
+
+pub struct VoxelGrid {
+    voxels: Vec<u8>, // Material indices, x fastest then y then z
+    materials: Vec<Arc<dyn Material>>,
+    dims: [usize; 3],
+    corner: Point3,
+    voxel_size: f64,
+    bbox: AABB,
+}
+
+impl VoxelGrid {
+    pub fn new(
+        dims: [usize; 3],
+        corner: Point3,
+        voxel_size: f64,
+        materials: Vec<Arc<dyn Material>>,
+    ) -> VoxelGrid {
+        // An empty grid of cubes with sides of `voxel_size`, starting at `corner`. A voxel
+        // holding index i is drawn with `materials[i - 1]`; index 0 is empty space.
+        assert!(
+            materials.len() < 256,
+            "voxel grids support up to 255 materials"
+        );
+        let size = voxel_size * Vec3::new(dims[0] as f64, dims[1] as f64, dims[2] as f64);
+        VoxelGrid {
+            voxels: vec![EMPTY_VOXEL; dims[0] * dims[1] * dims[2]],
+            materials,
+            dims,
+            corner,
+            voxel_size,
+            bbox: AABB::from_points(corner, corner + size),
+        }
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, z: usize, material_index: u8) {
+        assert!(
+            (material_index as usize) <= self.materials.len(),
+            "voxel material index out of range"
+        );
+        let i = self.index(x, y, z);
+        self.voxels[i] = material_index;
+    }
+
+    pub fn get(&self, x: usize, y: usize, z: usize) -> u8 {
+        self.voxels[self.index(x, y, z)]
+    }
+
+    fn index(&self, x: usize, y: usize, z: usize) -> usize {
+        x + self.dims[0] * (y + self.dims[1] * z)
+    }
+
+    fn material(&self, material_index: u8) -> Arc<dyn Material> {
+        self.materials[material_index as usize - 1].clone()
+    }
+
+    fn face_normal(axis: usize, step: &[i64; 3], entering: bool) -> Vec3 {
+        // The outward normal of a voxel being entered faces back along the ray's step on the
+        // crossed axis; the outward normal of a voxel being left faces along it.
+        let mut normal = [0.0; 3];
+        normal[axis] = if entering {
+            -step[axis] as f64
+        } else {
+            step[axis] as f64
+        };
+        return Vec3::new(normal[0], normal[1], normal[2]);
+    }
+
+    fn record(
+        &self,
+        r: &Ray,
+        t: f64,
+        axis: usize,
+        outward_normal: &Vec3,
+        material_index: u8,
+        rec: &mut HitRecord,
+    ) -> bool {
+        rec.t = t;
+        rec.p = r.at(t);
+
+        // Map each face onto [0,1] along its two other axes.
+        let u_axis = (axis + 1) % 3;
+        let v_axis = (axis + 2) % 3;
+        rec.u = ((rec.p[u_axis] - self.corner[u_axis]) / self.voxel_size).rem_euclid(1.0);
+        rec.v = ((rec.p[v_axis] - self.corner[v_axis]) / self.voxel_size).rem_euclid(1.0);
+
+        rec.set_face_normal(r, outward_normal);
+        rec.mat = self.material(material_index);
+        return true;
+    }
+}
+
+impl Hittable for VoxelGrid {
+    fn hit(&self, r: &Ray, ray_t: Interval, rec: &mut HitRecord) -> bool {
+        let clipped = match self.bbox.clip(r, &ray_t) {
+            Some(clipped) => clipped,
+            None => return false,
+        };
+
+        let origin = r.origin();
+        let dir = r.direction();
+        let entry = r.at(clipped.min);
+
+        // Amanatides–Woo traversal: visit voxels in the order the ray passes through them,
+        // tracking the ray parameter at which it next crosses a voxel boundary on each axis.
+        let mut cell = [0i64; 3];
+        let mut step = [0i64; 3];
+        let mut t_max = [f64::INFINITY; 3];
+        let mut t_delta = [f64::INFINITY; 3];
+        for axis in 0..3 {
+            let offset = (entry[axis] - self.corner[axis]) / self.voxel_size;
+            cell[axis] = (offset.floor() as i64).clamp(0, self.dims[axis] as i64 - 1);
+            if dir[axis] > 0.0 {
+                step[axis] = 1;
+                let next = self.corner[axis] + (cell[axis] + 1) as f64 * self.voxel_size;
+                t_max[axis] = (next - origin[axis]) / dir[axis];
+                t_delta[axis] = self.voxel_size / dir[axis];
+            } else if dir[axis] < 0.0 {
+                step[axis] = -1;
+                let next = self.corner[axis] + cell[axis] as f64 * self.voxel_size;
+                t_max[axis] = (next - origin[axis]) / dir[axis];
+                t_delta[axis] = -self.voxel_size / dir[axis];
+            }
+        }
+
+        let voxel_at = |cell: &[i64; 3]| -> u8 {
+            self.get(cell[0] as usize, cell[1] as usize, cell[2] as usize)
+        };
+
+        // A surface lies wherever the material changes. A ray from outside the grid that lands
+        // in a solid voxel hits the face it entered through. A ray that starts inside a solid
+        // voxel, such as one refracted into glass, carries on until that material ends.
+        let mut t = clipped.min;
+        let mut current = voxel_at(&cell);
+        let mut hit_axis = 0;
+        if clipped.min > ray_t.min {
+            let mut latest = f64::NEG_INFINITY;
+            for axis in 0..3 {
+                if step[axis] == 0 {
+                    continue;
+                }
+                let face = if step[axis] > 0 { 0.0 } else { 1.0 };
+                let plane = self.corner[axis] + (cell[axis] as f64 + face) * self.voxel_size;
+                let t_plane = (plane - origin[axis]) / dir[axis];
+                if t_plane > latest {
+                    latest = t_plane;
+                    hit_axis = axis;
+                }
+            }
+            if current != EMPTY_VOXEL && ray_t.surrounds(t) {
+                let normal = VoxelGrid::face_normal(hit_axis, &step, true);
+                return self.record(r, t, hit_axis, &normal, current, rec);
+            }
+        }
+
+        loop {
+            // Step into the neighbor across the nearest boundary.
+            let axis = if t_max[0] < t_max[1] {
+                if t_max[0] < t_max[2] {
+                    0
+                } else {
+                    2
+                }
+            } else if t_max[1] < t_max[2] {
+                1
+            } else {
+                2
+            };
+
+            t = t_max[axis];
+            cell[axis] += step[axis];
+            t_max[axis] += t_delta[axis];
+            if t >= clipped.max || cell[axis] < 0 || cell[axis] >= self.dims[axis] as i64 {
+                // Leaving the grid. Inside a solid that is the solid's far face. Rounding can
+                // put the last crossing just short of the clipped end, so either one counts.
+                let t_exit = t.min(clipped.max);
+                if current != EMPTY_VOXEL && ray_t.surrounds(t_exit) {
+                    let normal = VoxelGrid::face_normal(axis, &step, false);
+                    return self.record(r, t_exit, axis, &normal, current, rec);
+                }
+                return false;
+            }
+
+            let next = voxel_at(&cell);
+            if next == current {
+                continue;
+            }
+            if ray_t.surrounds(t) {
+                let entering = next != EMPTY_VOXEL;
+                let normal = VoxelGrid::face_normal(axis, &step, entering);
+                let material_index = if entering { next } else { current };
+                return self.record(r, t, axis, &normal, material_index, rec);
+            }
+            current = next;
+        }
+    }
+
+    fn bounding_box(&self) -> &AABB {
+        &self.bbox
+    }
+}
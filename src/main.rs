@@ -1,3 +1,5 @@
+#![allow(clippy::needless_return)]
+
 mod camera;
 mod color;
 mod hittables;
@@ -5,11 +7,36 @@ mod material;
 mod math;
 mod multithreaded_renderer;
 mod scenes;
+mod texture;
 mod util;
 
 use crate::scenes::moving_spheres;
 
 fn main() {
     // part1_final::render();
+    // primitives::render();
+    // instances::render();
+    // csg::render();
+    // sdf_shapes::render();
+    // terrain::render();
+    // voxels::render();
+    // curves::render();
+    // blobs::render();
+    // patches::render();
+    // subdivision::render();
+    // displacement::render();
+    // point_cloud::render();
+    // fog::render();
+    // volumes::render();
+    // textures::render();
+    // noise::render();
+    // mipmap::render();
+    // lights::render();
+    // cornell_box::render();
+    // environment::render();
+    // daylight::render();
+    // metals::render();
+    // frosted_glass::render();
+    // principled::render();
     moving_spheres::render();
 }
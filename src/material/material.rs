@@ -1,7 +1,15 @@
+use std::sync::Arc;
+
 use crate::color::Color;
 use crate::hittables::hittable::HitRecord;
-use crate::math::ray::Ray;
-use crate::math::vec3::Vec3;
+use crate::material::microfacet::{
+    fresnel_conductor, fresnel_dielectric, refraction_jacobian, transmission_half_vector,
+    TrowbridgeReitz,
+};
+use crate::math::onb::Onb;
+use crate::math::ray::{Ray, RayDifferentials};
+use crate::math::vec3::{Point3, Vec3};
+use crate::texture::texture::{SolidColor, Texture};
 use crate::util;
 
 pub trait Material: Send + Sync {
@@ -14,16 +22,43 @@ pub trait Material: Send + Sync {
     ) -> bool {
         return false;
     }
+
+    fn scattering_pdf(&self, _r_in: &Ray, _rec: &HitRecord, _scattered: &Ray) -> f64 {
+        // The density over solid angle that scatter() picks `scattered` with. Materials that
+        // pick their direction exactly, such as mirrors and glass, have none and return 0.
+        return 0.0;
+    }
+
+    fn has_density(&self) -> bool {
+        // Whether scattering_pdf() gives the density of the directions scatter() picks, so
+        // the direction can come from another strategy instead.
+        return false;
+    }
+
+    fn eval(&self, _r_in: &Ray, _rec: &HitRecord, _scattered: &Ray) -> Color {
+        // The fraction of light from `scattered` sent back along the incoming ray, per unit
+        // solid angle, cosine included. Scatter() attenuates by this over its density, so
+        // materials with a density must give both.
+        return Color::blank();
+    }
+
+    fn emitted(&self, _u: f64, _v: f64, _p: &Point3) -> Color {
+        return Color::blank();
+    }
 }
 
 #[derive(Clone)]
 pub struct Lambertian {
-    pub albedo: Color,
+    pub tex: Arc<dyn Texture>,
 }
 
 impl Lambertian {
     pub fn new(albedo: Color) -> Lambertian {
-        Lambertian { albedo }
+        Lambertian::from_texture(Arc::new(SolidColor::new(albedo)))
+    }
+
+    pub fn from_texture(tex: Arc<dyn Texture>) -> Lambertian {
+        Lambertian { tex }
     }
 }
 
@@ -43,20 +78,38 @@ impl Material for Lambertian {
         }
 
         *scattered = Ray::new_timed(rec.p, scatter_direction, r_in.time());
-        *attenuation = self.albedo;
+        *attenuation = self.tex.sample(rec);
         return true;
     }
+
+    fn scattering_pdf(&self, _r_in: &Ray, rec: &HitRecord, scattered: &Ray) -> f64 {
+        // Scattering towards the normal plus a random unit vector gives a cosine density.
+        let cos_theta = Vec3::dot(&rec.normal, &Vec3::unit_vector(scattered.direction()));
+        return cos_theta.max(0.0) / util::PI;
+    }
+
+    fn has_density(&self) -> bool {
+        return true;
+    }
+
+    fn eval(&self, r_in: &Ray, rec: &HitRecord, scattered: &Ray) -> Color {
+        return self.tex.sample(rec) * self.scattering_pdf(r_in, rec, scattered);
+    }
 }
 
 #[derive(Clone)]
 pub struct Metal {
-    pub albedo: Color,
+    pub tex: Arc<dyn Texture>,
     pub fuzz: f64,
 }
 
 impl Metal {
     pub fn new(albedo: Color, fuzz: f64) -> Metal {
-        Metal { albedo, fuzz }
+        Metal::from_texture(Arc::new(SolidColor::new(albedo)), fuzz)
+    }
+
+    pub fn from_texture(tex: Arc<dyn Texture>, fuzz: f64) -> Metal {
+        Metal { tex, fuzz }
     }
 }
 
@@ -71,11 +124,132 @@ impl Material for Metal {
         let mut reflected = Vec3::reflect(&r_in.direction(), &rec.normal);
         reflected = Vec3::unit_vector(reflected) + (self.fuzz * Vec3::random_unit_vector());
         *scattered = Ray::new_timed(rec.p, reflected, r_in.time());
-        *attenuation = self.albedo;
+        if self.fuzz == 0.0 {
+            scattered.differentials = reflected_differentials(r_in, rec, &reflected);
+        }
+        *attenuation = self.tex.sample(rec);
         return Vec3::dot(&scattered.direction(), &rec.normal) > 0.0;
     }
 }
 
+// A metal with microfacet roughness, reflecting by its complex index of refraction eta + ik
+// for red, green and blue. Unlike Metal's fuzz, light is only lost where the surface really
+// shadows itself, and the edges brighten as real metals do.
+pub struct Conductor {
+    pub eta: Color,
+    pub k: Color,
+    pub distribution: TrowbridgeReitz,
+}
+
+impl Conductor {
+    pub fn new(eta: Color, k: Color, roughness: f64) -> Conductor {
+        Conductor {
+            eta,
+            k,
+            distribution: TrowbridgeReitz::new(roughness),
+        }
+    }
+
+    // Indices for red, green and blue light of about 650, 550 and 450 nanometers.
+
+    pub fn gold(roughness: f64) -> Conductor {
+        Conductor::new(
+            Color::new(0.143, 0.374, 1.442),
+            Color::new(3.983, 2.385, 1.603),
+            roughness,
+        )
+    }
+
+    pub fn copper(roughness: f64) -> Conductor {
+        Conductor::new(
+            Color::new(0.200, 0.924, 1.102),
+            Color::new(3.912, 2.452, 2.142),
+            roughness,
+        )
+    }
+
+    pub fn silver(roughness: f64) -> Conductor {
+        Conductor::new(
+            Color::new(0.155, 0.116, 0.138),
+            Color::new(4.828, 3.122, 2.147),
+            roughness,
+        )
+    }
+
+    pub fn aluminum(roughness: f64) -> Conductor {
+        Conductor::new(
+            Color::new(1.657, 0.880, 0.521),
+            Color::new(9.224, 6.269, 4.837),
+            roughness,
+        )
+    }
+}
+
+impl Material for Conductor {
+    fn scatter(
+        &self,
+        r_in: &Ray,
+        rec: &HitRecord,
+        attenuation: &mut Color,
+        scattered: &mut Ray,
+    ) -> bool {
+        let unit_direction = Vec3::unit_vector(r_in.direction());
+        if self.distribution.is_smooth() {
+            let reflected = Vec3::reflect(&unit_direction, &rec.normal);
+            let cos_theta = Vec3::dot(&-&unit_direction, &rec.normal);
+            *scattered = Ray::new_timed(rec.p, reflected, r_in.time());
+            scattered.differentials = reflected_differentials(r_in, rec, &reflected);
+            *attenuation = fresnel_conductor(cos_theta, &self.eta, &self.k);
+            return true;
+        }
+
+        // Reflect off a visible microfacet. What's left of f cos / pdf is the Fresnel term
+        // and the chance the reflection isn't shadowed on its way out.
+        let uvw = Onb::new(&rec.normal);
+        let wo = uvw.to_local(&-&unit_direction);
+        if wo.z() <= 0.0 {
+            return false;
+        }
+        let h = self.distribution.sample_visible_normal(&wo);
+        let wi = Vec3::reflect(&-&wo, &h);
+        if wi.z() <= 0.0 {
+            return false;
+        }
+
+        *scattered = Ray::new_timed(rec.p, uvw.transform(&wi), r_in.time());
+        *attenuation = fresnel_conductor(Vec3::dot(&wo, &h), &self.eta, &self.k)
+            * self.distribution.g2(&wo, &wi)
+            / self.distribution.g1(&wo);
+        return true;
+    }
+
+    fn scattering_pdf(&self, r_in: &Ray, rec: &HitRecord, scattered: &Ray) -> f64 {
+        if self.distribution.is_smooth() {
+            return 0.0;
+        }
+        let uvw = Onb::new(&rec.normal);
+        let wo = uvw.to_local(&-&Vec3::unit_vector(r_in.direction()));
+        let wi = uvw.to_local(&Vec3::unit_vector(scattered.direction()));
+        return self.distribution.pdf(&wo, &wi);
+    }
+
+    fn has_density(&self) -> bool {
+        return !self.distribution.is_smooth();
+    }
+
+    fn eval(&self, r_in: &Ray, rec: &HitRecord, scattered: &Ray) -> Color {
+        let uvw = Onb::new(&rec.normal);
+        let wo = uvw.to_local(&-&Vec3::unit_vector(r_in.direction()));
+        let wi = uvw.to_local(&Vec3::unit_vector(scattered.direction()));
+        if wo.z() <= 0.0 || wi.z() <= 0.0 {
+            return Color::blank();
+        }
+        let h = Vec3::unit_vector(wo + wi);
+        return fresnel_conductor(Vec3::dot(&wo, &h), &self.eta, &self.k)
+            * self.distribution.reflection(&wo, &wi);
+    }
+}
+
 pub struct Dielectric {
     pub refraction_index: f64,
 }
@@ -113,15 +287,546 @@ impl Material for Dielectric {
         let sin_theta = (1.0 - cos_theta * cos_theta).sqrt();
 
         let cannot_refract = ri * sin_theta > 1.0;
-        let direction;
+        let reflect =
+            cannot_refract || Dielectric::reflectance(cos_theta, ri) > util::random_double();
+        let direction = if reflect {
+            Vec3::reflect(&unit_direction, &rec.normal)
+        } else {
+            Vec3::refract(&unit_direction, &rec.normal, ri)
+        };
 
-        if cannot_refract || Dielectric::reflectance(cos_theta, ri) > util::random_double() {
-            direction = Vec3::reflect(&unit_direction, &rec.normal);
+        *scattered = Ray::new_timed(rec.p, direction, r_in.time());
+        scattered.differentials = if reflect {
+            reflected_differentials(r_in, rec, &direction)
         } else {
-            direction = Vec3::refract(&unit_direction, &rec.normal, ri);
+            refracted_differentials(r_in, rec, &direction, ri)
+        };
+        return true;
+    }
+}
+
+// Glass with a microfacet surface, after Walter et al., "Microfacet Models for Refraction
+// through Rough Surfaces". Each ray picks a visible microfacet, then reflects or refracts
+// through it by the exact Fresnel reflectance, so a rough surface blurs what's seen through
+// it as well as what it reflects. Roughness 0 behaves like Dielectric.
+pub struct RoughDielectric {
+    pub refraction_index: f64,
+    pub distribution: TrowbridgeReitz,
+}
+
+impl RoughDielectric {
+    pub fn new(refraction_index: f64, roughness: f64) -> RoughDielectric {
+        RoughDielectric {
+            refraction_index,
+            distribution: TrowbridgeReitz::new(roughness),
         }
+    }
 
-        *scattered = Ray::new_timed(rec.p, direction, r_in.time());
+    fn local_directions(&self, r_in: &Ray, rec: &HitRecord, scattered: &Ray) -> (Vec3, Vec3, f64) {
+        // The incoming and scattered directions in the shading frame, both pointing away from
+        // the surface, and the index ratio across it from the incoming side.
+        let uvw = Onb::new(&rec.normal);
+        let wo = uvw.to_local(&-&Vec3::unit_vector(r_in.direction()));
+        let wi = uvw.to_local(&Vec3::unit_vector(scattered.direction()));
+        let eta = if rec.front_face {
+            self.refraction_index
+        } else {
+            1.0 / self.refraction_index
+        };
+        return (wo, wi, eta);
+    }
+}
+
+impl Material for RoughDielectric {
+    fn scatter(
+        &self,
+        r_in: &Ray,
+        rec: &HitRecord,
+        attenuation: &mut Color,
+        scattered: &mut Ray,
+    ) -> bool {
+        if self.distribution.is_smooth() {
+            return Dielectric::new(self.refraction_index).scatter(
+                r_in,
+                rec,
+                attenuation,
+                scattered,
+            );
+        }
+
+        let uvw = Onb::new(&rec.normal);
+        let wo = uvw.to_local(&-&Vec3::unit_vector(r_in.direction()));
+        if wo.z() <= 0.0 {
+            return false;
+        }
+        let eta = if rec.front_face {
+            self.refraction_index
+        } else {
+            1.0 / self.refraction_index
+        };
+
+        // Choosing between reflection and refraction by the Fresnel reflectance cancels it
+        // from the weight, leaving only the chance of escaping the microsurface.
+        let h = self.distribution.sample_visible_normal(&wo);
+        let cos_theta = Vec3::dot(&wo, &h);
+        let wi = if fresnel_dielectric(cos_theta, eta) > util::random_double() {
+            let wi = Vec3::reflect(&-&wo, &h);
+            if wi.z() <= 0.0 {
+                return false;
+            }
+            wi
+        } else {
+            let wi = Vec3::refract(&-&wo, &h, 1.0 / eta);
+            if wi.z() >= 0.0 {
+                return false;
+            }
+            wi
+        };
+
+        *scattered = Ray::new_timed(rec.p, uvw.transform(&wi), r_in.time());
+        let g = self.distribution.g2(&wo, &wi) / self.distribution.g1(&wo);
+        *attenuation = Color::new(g, g, g);
+        return true;
+    }
+
+    fn scattering_pdf(&self, r_in: &Ray, rec: &HitRecord, scattered: &Ray) -> f64 {
+        if self.distribution.is_smooth() {
+            return 0.0;
+        }
+        let (wo, wi, eta) = self.local_directions(r_in, rec, scattered);
+        if wo.z() <= 0.0 {
+            return 0.0;
+        }
+
+        if wi.z() > 0.0 {
+            let h = Vec3::unit_vector(wo + wi);
+            let f = fresnel_dielectric(Vec3::dot(&wo, &h), eta);
+            return f * self.distribution.pdf(&wo, &wi);
+        }
+
+        let h = match transmission_half_vector(&wo, &wi, eta) {
+            Some(h) => h,
+            None => return 0.0,
+        };
+        let f = fresnel_dielectric(Vec3::dot(&wo, &h), eta);
+        return (1.0 - f)
+            * self.distribution.visible_normal_pdf(&wo, &h)
+            * refraction_jacobian(&wo, &wi, &h, eta);
+    }
+
+    fn has_density(&self) -> bool {
+        return !self.distribution.is_smooth();
+    }
+
+    fn eval(&self, r_in: &Ray, rec: &HitRecord, scattered: &Ray) -> Color {
+        if self.distribution.is_smooth() {
+            return Color::blank();
+        }
+        let (wo, wi, eta) = self.local_directions(r_in, rec, scattered);
+        if wo.z() <= 0.0 {
+            return Color::blank();
+        }
+
+        let value = if wi.z() > 0.0 {
+            let h = Vec3::unit_vector(wo + wi);
+            let f = fresnel_dielectric(Vec3::dot(&wo, &h), eta);
+            f * self.distribution.reflection(&wo, &wi)
+        } else {
+            let h = match transmission_half_vector(&wo, &wi, eta) {
+                Some(h) => h,
+                None => return Color::blank(),
+            };
+            let f = fresnel_dielectric(Vec3::dot(&wo, &h), eta);
+            (1.0 - f) * self.distribution.transmission(&wo, &wi, &h, eta)
+        };
+        return Color::new(value, value, value);
+    }
+}
+
+// The clearcoat's fixed roughness, glossy but not mirror sharp.
+const CLEARCOAT_ROUGHNESS: f64 = 0.1;
+
+// One material for most surfaces, after Burley's "Physically-Based Shading at Disney" and
+// its 2015 follow-up. Base color tints the diffuse, the metal's reflection and the glass's
+// transmission. Metallic blends from a dielectric to a conductor, specular sets the
+// dielectric's reflectance (0.5 is an index of refraction of 1.5), sheen adds a soft glow at
+// grazing angles as on cloth, clearcoat adds a second glossy layer as on car paint,
+// transmission turns the dielectric into glass, and anisotropic stretches the highlight
+// along the surface's u direction as on brushed metal. Every parameter is a texture, and the
+// single-number ones read their red channel.
+pub struct Principled {
+    pub base_color: Arc<dyn Texture>,
+    pub metallic: Arc<dyn Texture>,
+    pub roughness: Arc<dyn Texture>,
+    pub specular: Arc<dyn Texture>,
+    pub sheen: Arc<dyn Texture>,
+    pub clearcoat: Arc<dyn Texture>,
+    pub transmission: Arc<dyn Texture>,
+    pub anisotropic: Arc<dyn Texture>,
+}
+
+impl Principled {
+    pub fn new(base_color: Color) -> Principled {
+        Principled::from_texture(Arc::new(SolidColor::new(base_color)))
+    }
+
+    pub fn from_texture(base_color: Arc<dyn Texture>) -> Principled {
+        // A slightly rough plastic. Set the other fields to change it.
+        let value = |v: f64| -> Arc<dyn Texture> { Arc::new(SolidColor::from_value(v)) };
+        Principled {
+            base_color,
+            metallic: value(0.0),
+            roughness: value(0.5),
+            specular: value(0.5),
+            sheen: value(0.0),
+            clearcoat: value(0.0),
+            transmission: value(0.0),
+            anisotropic: value(0.0),
+        }
+    }
+
+    fn lobes(&self, r_in: &Ray, rec: &HitRecord) -> PrincipledLobes {
+        let param = |tex: &Arc<dyn Texture>| tex.sample(rec).x().clamp(0.0, 1.0);
+        let base = self.base_color.sample(rec);
+        let metallic = param(&self.metallic);
+        let roughness = param(&self.roughness);
+        let specular = param(&self.specular);
+
+        // Specular 0.5 gives the 4% reflectance of an index of refraction of 1.5.
+        let dielectric_f0 = 0.08 * specular;
+        let sqrt_f0 = dielectric_f0.sqrt();
+        let eta = ((1.0 + sqrt_f0) / (1.0 - sqrt_f0)).max(1.001);
+
+        let frame = Onb::from_tangent(&rec.normal, &rec.dpdu);
+        PrincipledLobes {
+            wo: frame.to_local(&-&Vec3::unit_vector(r_in.direction())),
+            frame,
+            base,
+            metallic,
+            roughness,
+            sheen: param(&self.sheen),
+            clearcoat: param(&self.clearcoat),
+            transmission: param(&self.transmission),
+            eta: if rec.front_face { eta } else { 1.0 / eta },
+            specular_lobe: TrowbridgeReitz::anisotropic(roughness, param(&self.anisotropic)),
+            clearcoat_lobe: TrowbridgeReitz::anisotropic(CLEARCOAT_ROUGHNESS, 0.0),
+        }
+    }
+}
+
+impl Material for Principled {
+    fn scatter(
+        &self,
+        r_in: &Ray,
+        rec: &HitRecord,
+        attenuation: &mut Color,
+        scattered: &mut Ray,
+    ) -> bool {
+        // Pick one lobe to sample, then weight by all of them together, so each direction
+        // counts every lobe that could have produced it.
+        let lobes = self.lobes(r_in, rec);
+        let wi = match lobes.sample() {
+            Some(wi) => wi,
+            None => return false,
+        };
+        let pdf = lobes.pdf(&wi);
+        if pdf <= 0.0 {
+            return false;
+        }
+
+        *scattered = Ray::new_timed(rec.p, lobes.frame.transform(&wi), r_in.time());
+        *attenuation = lobes.eval(&wi) / pdf;
+        return true;
+    }
+
+    fn scattering_pdf(&self, r_in: &Ray, rec: &HitRecord, scattered: &Ray) -> f64 {
+        let lobes = self.lobes(r_in, rec);
+        let wi = lobes
+            .frame
+            .to_local(&Vec3::unit_vector(scattered.direction()));
+        return lobes.pdf(&wi);
+    }
+
+    fn has_density(&self) -> bool {
         return true;
     }
+
+    fn eval(&self, r_in: &Ray, rec: &HitRecord, scattered: &Ray) -> Color {
+        let lobes = self.lobes(r_in, rec);
+        let wi = lobes
+            .frame
+            .to_local(&Vec3::unit_vector(scattered.direction()));
+        return lobes.eval(&wi);
+    }
+}
+
+// A Principled material's parameters at one hit, with directions in a shading frame whose
+// u axis follows the surface's u direction.
+struct PrincipledLobes {
+    frame: Onb,
+    wo: Vec3,
+    base: Color,
+    metallic: f64,
+    roughness: f64,
+    sheen: f64,
+    clearcoat: f64,
+    transmission: f64,
+    eta: f64,
+    specular_lobe: TrowbridgeReitz,
+    clearcoat_lobe: TrowbridgeReitz,
+}
+
+impl PrincipledLobes {
+    fn fresnel(&self, cosine: f64) -> Color {
+        // The specular reflectance. The dielectric's is exact, so it reflects everything the
+        // transmission can't pass, light trapped inside glass included. The metal's follows
+        // Schlick from the base color.
+        let dielectric = fresnel_dielectric(cosine, self.eta);
+        return (1.0 - self.metallic) * Color::new(dielectric, dielectric, dielectric)
+            + self.metallic * schlick(&self.base, cosine);
+    }
+
+    fn weights(&self) -> [f64; 4] {
+        // The chance of sampling the diffuse, specular, clearcoat and transmission lobes,
+        // roughly in proportion to how much light each returns.
+        let wo = &self.wo;
+        let diffuse = (1.0 - self.metallic) * (1.0 - self.transmission);
+        let specular = luminance(&self.fresnel(wo.z())).max(0.05);
+        let clearcoat = 0.25 * self.clearcoat * schlick_scalar(0.04, wo.z());
+        let transmission = (1.0 - self.metallic)
+            * self.transmission
+            * (1.0 - fresnel_dielectric(wo.z(), self.eta));
+        let total = diffuse + specular + clearcoat + transmission;
+        return [diffuse, specular, clearcoat, transmission].map(|w| w / total);
+    }
+
+    fn sample(&self) -> Option<Vec3> {
+        let wo = &self.wo;
+        if wo.z() <= 0.0 {
+            return None;
+        }
+
+        let [diffuse, specular, clearcoat, _] = self.weights();
+        let xi = util::random_double();
+        if xi < diffuse {
+            let direction = Vec3::new(0.0, 0.0, 1.0) + Vec3::random_unit_vector();
+            if direction.near_zero() {
+                return Some(Vec3::new(0.0, 0.0, 1.0));
+            }
+            return Some(Vec3::unit_vector(direction));
+        }
+        // Reflections that end up below the surface, and refractions above it, are lost.
+        if xi < diffuse + specular + clearcoat {
+            let lobe = if xi < diffuse + specular {
+                &self.specular_lobe
+            } else {
+                &self.clearcoat_lobe
+            };
+            let h = lobe.sample_visible_normal(wo);
+            let wi = Vec3::reflect(&-wo, &h);
+            return if wi.z() > 0.0 { Some(wi) } else { None };
+        }
+
+        // Refract through a visible microfacet, unless the light is trapped inside.
+        let h = self.specular_lobe.sample_visible_normal(wo);
+        if fresnel_dielectric(Vec3::dot(wo, &h), self.eta) >= 1.0 {
+            return None;
+        }
+        let wi = Vec3::refract(&-wo, &h, 1.0 / self.eta);
+        return if wi.z() < 0.0 { Some(wi) } else { None };
+    }
+
+    fn pdf(&self, wi: &Vec3) -> f64 {
+        let wo = &self.wo;
+        if wo.z() <= 0.0 {
+            return 0.0;
+        }
+
+        let [diffuse, specular, clearcoat, transmission] = self.weights();
+        if wi.z() > 0.0 {
+            return diffuse * wi.z() / util::PI
+                + specular * self.specular_lobe.pdf(wo, wi)
+                + clearcoat * self.clearcoat_lobe.pdf(wo, wi);
+        }
+        return match transmission_half_vector(wo, wi, self.eta) {
+            Some(h) => {
+                transmission
+                    * self.specular_lobe.visible_normal_pdf(wo, &h)
+                    * refraction_jacobian(wo, wi, &h, self.eta)
+            }
+            None => 0.0,
+        };
+    }
+
+    fn eval(&self, wi: &Vec3) -> Color {
+        let wo = &self.wo;
+        if wo.z() <= 0.0 {
+            return Color::blank();
+        }
+
+        if wi.z() < 0.0 {
+            let h = match transmission_half_vector(wo, wi, self.eta) {
+                Some(h) => h,
+                None => return Color::blank(),
+            };
+            let f = fresnel_dielectric(Vec3::dot(wo, &h), self.eta);
+            let weight = (1.0 - self.metallic) * self.transmission * (1.0 - f);
+            return weight * self.specular_lobe.transmission(wo, wi, &h, self.eta) * self.base;
+        }
+
+        let h = Vec3::unit_vector(*wo + *wi);
+        let cos_d = Vec3::dot(wi, &h);
+
+        // Burley's diffuse, which brightens rough surfaces at grazing angles and darkens
+        // smooth ones, and the sheen, tinted halfway towards the base color's hue.
+        let fd90 = 0.5 + 2.0 * self.roughness * cos_d * cos_d;
+        let fl = (1.0 - wi.z()).powi(5);
+        let fv = (1.0 - wo.z()).powi(5);
+        let fd = (1.0 + (fd90 - 1.0) * fl) * (1.0 + (fd90 - 1.0) * fv);
+        let tint = if luminance(&self.base) > 0.0 {
+            self.base / luminance(&self.base)
+        } else {
+            Color::new(1.0, 1.0, 1.0)
+        };
+        let sheen_color = 0.5 * (Color::new(1.0, 1.0, 1.0) + tint);
+        let sheen = self.sheen * (1.0 - cos_d).powi(5) * sheen_color;
+        let diffuse = (1.0 - self.metallic)
+            * (1.0 - self.transmission)
+            * (fd * self.base / util::PI + sheen)
+            * wi.z();
+
+        let specular = self.fresnel(Vec3::dot(wo, &h)) * self.specular_lobe.reflection(wo, wi);
+        let clearcoat = 0.25
+            * self.clearcoat
+            * schlick_scalar(0.04, Vec3::dot(wo, &h))
+            * self.clearcoat_lobe.reflection(wo, wi);
+        return diffuse + specular + Color::new(clearcoat, clearcoat, clearcoat);
+    }
+}
+
+fn schlick(f0: &Color, cosine: f64) -> Color {
+    let t = (1.0 - cosine.clamp(0.0, 1.0)).powi(5);
+    return *f0 + t * (Color::new(1.0, 1.0, 1.0) - *f0);
+}
+
+fn schlick_scalar(f0: f64, cosine: f64) -> f64 {
+    return f0 + (1.0 - f0) * (1.0 - cosine.clamp(0.0, 1.0)).powi(5);
+}
+
+fn luminance(c: &Color) -> f64 {
+    return 0.2126 * c.x() + 0.7152 * c.y() + 0.0722 * c.z();
+}
+
+pub struct Isotropic {
+    pub tex: Arc<dyn Texture>,
+}
+
+impl Isotropic {
+    pub fn from_texture(tex: Arc<dyn Texture>) -> Isotropic {
+        Isotropic { tex }
+    }
+}
+
+impl Material for Isotropic {
+    fn scatter(
+        &self,
+        r_in: &Ray,
+        rec: &HitRecord,
+        attenuation: &mut Color,
+        scattered: &mut Ray,
+    ) -> bool {
+        // Scatter in a uniformly random direction, as a medium with no preferred one would.
+        *scattered = Ray::new_timed(rec.p, Vec3::random_unit_vector(), r_in.time());
+        *attenuation = self.tex.sample(rec);
+        return true;
+    }
+
+    fn scattering_pdf(&self, _r_in: &Ray, _rec: &HitRecord, _scattered: &Ray) -> f64 {
+        return 1.0 / (4.0 * util::PI);
+    }
+
+    fn has_density(&self) -> bool {
+        return true;
+    }
+
+    fn eval(&self, _r_in: &Ray, rec: &HitRecord, _scattered: &Ray) -> Color {
+        return self.tex.sample(rec) / (4.0 * util::PI);
+    }
+}
+
+// A surface that gives off light and reflects none. It glows the same from both sides.
+pub struct DiffuseLight {
+    pub tex: Arc<dyn Texture>,
+}
+
+impl DiffuseLight {
+    pub fn new(emit: Color) -> DiffuseLight {
+        DiffuseLight::from_texture(Arc::new(SolidColor::new(emit)))
+    }
+
+    pub fn from_texture(tex: Arc<dyn Texture>) -> DiffuseLight {
+        DiffuseLight { tex }
+    }
+}
+
+impl Material for DiffuseLight {
+    fn emitted(&self, u: f64, v: f64, p: &Point3) -> Color {
+        return self.tex.value(u, v, p);
+    }
+}
+
+// The differentials of a ray leaving a perfectly smooth surface, after Igehy's "Tracing Ray
+// Differentials". The surface is treated as flat across the footprint, so curvature
+// doesn't widen or narrow the offsets. Both return None if the incoming ray has none.
+
+fn offsets(r_in: &Ray, rec: &HitRecord) -> Option<(Vec3, Vec3, Vec3, Vec3)> {
+    // The offset origins on the surface, and the change in the normalized incoming
+    // direction wo = -d from the main ray to each offset ray.
+    let d = r_in.differentials?;
+    let surface = rec.differentials?;
+    let wo = -&Vec3::unit_vector(r_in.direction());
+    let dwodx = -&Vec3::unit_vector(d.rx_direction) - wo;
+    let dwody = -&Vec3::unit_vector(d.ry_direction) - wo;
+    return Some((rec.p + surface.dpdx, rec.p + surface.dpdy, dwodx, dwody));
+}
+
+fn reflected_differentials(r_in: &Ray, rec: &HitRecord, wi: &Vec3) -> Option<RayDifferentials> {
+    let (rx_origin, ry_origin, dwodx, dwody) = offsets(r_in, rec)?;
+    let wi = Vec3::unit_vector(*wi);
+    let n = rec.normal;
+    let reflect = |dwo: Vec3| wi - dwo + 2.0 * Vec3::dot(&dwo, &n) * n;
+    return Some(RayDifferentials {
+        rx_origin,
+        rx_direction: reflect(dwodx),
+        ry_origin,
+        ry_direction: reflect(dwody),
+    });
+}
+
+fn refracted_differentials(
+    r_in: &Ray,
+    rec: &HitRecord,
+    wi: &Vec3,
+    eta: f64,
+) -> Option<RayDifferentials> {
+    // eta is the ratio of refractive indices, incoming side over outgoing side.
+    let (rx_origin, ry_origin, dwodx, dwody) = offsets(r_in, rec)?;
+    let wi = Vec3::unit_vector(*wi);
+    let n = rec.normal;
+    let wo = -&Vec3::unit_vector(r_in.direction());
+    let cos_o = Vec3::dot(&wo, &n);
+    let cos_i = Vec3::dot(&wi, &n).abs();
+    if cos_i < 1e-8 {
+        return None;
+    }
+    let refract = |dwo: Vec3| {
+        let dmu = (eta - eta * eta * cos_o / cos_i) * Vec3::dot(&dwo, &n);
+        wi - eta * dwo + dmu * n
+    };
+    return Some(RayDifferentials {
+        rx_origin,
+        rx_direction: refract(dwodx),
+        ry_origin,
+        ry_direction: refract(dwody),
+    });
 }
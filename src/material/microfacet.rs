@@ -0,0 +1,200 @@
+use crate::color::Color;
+use crate::math::vec3::Vec3;
+use crate::util;
+
+// The GGX (Trowbridge-Reitz) distribution of microfacet normals, with widths `alpha_x`
+// and `alpha_y` along the two tangents. Directions are in a shading frame with the surface
+// normal along +z.
+#[derive(Clone, Copy)]
+pub struct TrowbridgeReitz {
+    alpha_x: f64,
+    alpha_y: f64,
+}
+
+impl TrowbridgeReitz {
+    pub fn new(roughness: f64) -> TrowbridgeReitz {
+        // Roughness runs from 0 for a mirror to 1 for a very rough surface. Squaring it
+        // spreads the visible change in blur evenly over the range.
+        let roughness = roughness.clamp(0.0, 1.0);
+        let alpha = roughness * roughness;
+        TrowbridgeReitz {
+            alpha_x: alpha,
+            alpha_y: alpha,
+        }
+    }
+
+    pub fn anisotropic(roughness: f64, anisotropy: f64) -> TrowbridgeReitz {
+        // Stretches the highlight along the x tangent as `anisotropy` goes from 0 to 1, as
+        // in Burley's "Physically-Based Shading at Disney". The widths stay above the point
+        // where the lobe becomes too sharp to evaluate, so the surface is never a perfect
+        // mirror.
+        let roughness = roughness.clamp(0.0, 1.0);
+        let aspect = (1.0 - 0.9 * anisotropy.clamp(0.0, 1.0)).sqrt();
+        let alpha = roughness * roughness;
+        TrowbridgeReitz {
+            alpha_x: (alpha / aspect).max(1e-3),
+            alpha_y: (alpha * aspect).max(1e-3),
+        }
+    }
+
+    pub fn is_smooth(&self) -> bool {
+        // Below this width the lobe is too narrow to sample or evaluate reliably, so
+        // materials treat the surface as a perfect mirror.
+        self.alpha_x.max(self.alpha_y) < 1e-3
+    }
+
+    pub fn d(&self, h: &Vec3) -> f64 {
+        // The density of microfacet normals, per unit projected area.
+        if h.z() <= 0.0 {
+            return 0.0;
+        }
+        let (ax, ay) = (self.alpha_x, self.alpha_y);
+        let e = (h.x() / ax).powi(2) + (h.y() / ay).powi(2) + h.z() * h.z();
+        return 1.0 / (util::PI * ax * ay * e * e);
+    }
+
+    fn lambda(&self, w: &Vec3) -> f64 {
+        let cos2 = w.z() * w.z();
+        if cos2 <= 0.0 {
+            return util::INFINITY;
+        }
+        let (ax, ay) = (self.alpha_x, self.alpha_y);
+        let tan2_alpha2 = ((ax * w.x()).powi(2) + (ay * w.y()).powi(2)) / cos2;
+        return 0.5 * (-1.0 + (1.0 + tan2_alpha2).sqrt());
+    }
+
+    pub fn g1(&self, w: &Vec3) -> f64 {
+        // Smith's masking: the fraction of microfacets facing `w` that `w` can see.
+        return 1.0 / (1.0 + self.lambda(w));
+    }
+
+    pub fn g2(&self, wo: &Vec3, wi: &Vec3) -> f64 {
+        // Height-correlated masking and shadowing for the pair of directions.
+        return 1.0 / (1.0 + self.lambda(wo) + self.lambda(wi));
+    }
+
+    pub fn sample_visible_normal(&self, wo: &Vec3) -> Vec3 {
+        // Picks a microfacet normal in proportion to how much of it `wo` sees, after Heitz,
+        // "Sampling the GGX Distribution of Visible Normals". Only normals `wo` can reach
+        // are picked, so no samples are wasted on faces turned away.
+        let (ax, ay) = (self.alpha_x, self.alpha_y);
+        let vh = Vec3::unit_vector(Vec3::new(ax * wo.x(), ay * wo.y(), wo.z()));
+        let lensq = vh.x() * vh.x() + vh.y() * vh.y();
+        let t1 = if lensq > 0.0 {
+            Vec3::new(-vh.y(), vh.x(), 0.0) / lensq.sqrt()
+        } else {
+            Vec3::new(1.0, 0.0, 0.0)
+        };
+        let t2 = Vec3::cross(vh, t1);
+
+        // A point on a disk, squeezed towards the half facing away from `wo`.
+        let r = util::random_double().sqrt();
+        let phi = 2.0 * util::PI * util::random_double();
+        let p1 = r * phi.cos();
+        let s = 0.5 * (1.0 + vh.z());
+        let p2 = (1.0 - s) * (1.0 - p1 * p1).sqrt() + s * r * phi.sin();
+
+        // Lift it onto the hemisphere around vh, then stretch back to the rough surface.
+        let nh = p1 * t1 + p2 * t2 + (1.0 - p1 * p1 - p2 * p2).max(0.0).sqrt() * vh;
+        return Vec3::unit_vector(Vec3::new(ax * nh.x(), ay * nh.y(), nh.z().max(1e-6)));
+    }
+
+    pub fn visible_normal_pdf(&self, wo: &Vec3, h: &Vec3) -> f64 {
+        // The solid angle density of sample_visible_normal() picking `h`.
+        if wo.z() <= 0.0 {
+            return 0.0;
+        }
+        return self.g1(wo) * self.d(h) * Vec3::dot(wo, h).max(0.0) / wo.z();
+    }
+
+    pub fn pdf(&self, wo: &Vec3, wi: &Vec3) -> f64 {
+        // The solid angle density of reflecting `wo` into `wi` off a normal from
+        // sample_visible_normal().
+        if wo.z() <= 0.0 || wi.z() <= 0.0 {
+            return 0.0;
+        }
+        let h = Vec3::unit_vector(*wo + *wi);
+        return self.visible_normal_pdf(wo, &h) / (4.0 * Vec3::dot(wo, &h));
+    }
+
+    pub fn reflection(&self, wo: &Vec3, wi: &Vec3) -> f64 {
+        // D G / (4 cos_o cos_i), times cos_i, leaving the Fresnel term to the caller.
+        if wo.z() <= 0.0 || wi.z() <= 0.0 {
+            return 0.0;
+        }
+        let h = Vec3::unit_vector(*wo + *wi);
+        return self.d(&h) * self.g2(wo, wi) / (4.0 * wo.z());
+    }
+
+    pub fn transmission(&self, wo: &Vec3, wi: &Vec3, h: &Vec3, eta: f64) -> f64 {
+        // Walter's transmission term through microfacet `h`, times |cos_i|, leaving the
+        // Fresnel term to the caller. Like Dielectric, this leaves out the change in
+        // radiance from squeezing light into a denser medium.
+        return self.d(h) * self.g2(wo, wi) * Vec3::dot(wo, h) / wo.z()
+            * refraction_jacobian(wo, wi, h, eta);
+    }
+}
+
+pub fn transmission_half_vector(wo: &Vec3, wi: &Vec3, eta: f64) -> Option<Vec3> {
+    // The microfacet normal that refracts wo into wi, where eta is the index of refraction
+    // on wi's side over wo's, if there is one.
+    let mut h = Vec3::unit_vector(*wo + eta * *wi);
+    if h.z() < 0.0 {
+        h = -&h;
+    }
+    if Vec3::dot(wo, &h) <= 0.0 || Vec3::dot(wi, &h) >= 0.0 {
+        return None;
+    }
+    return Some(h);
+}
+
+pub fn refraction_jacobian(wo: &Vec3, wi: &Vec3, h: &Vec3, eta: f64) -> f64 {
+    // How fast the refracted direction wi moves as its microfacet normal h does, turning a
+    // density over normals into one over refracted directions.
+    let (cos_o, cos_i) = (Vec3::dot(wo, h), Vec3::dot(wi, h));
+    let denom = cos_o + eta * cos_i;
+    return eta * eta * cos_i.abs() / (denom * denom);
+}
+
+pub fn fresnel_conductor(cos_theta: f64, eta: &Color, k: &Color) -> Color {
+    // The unpolarized reflectance of a metal with complex index of refraction eta + ik, one
+    // color channel at a time.
+    let c = cos_theta.clamp(0.0, 1.0);
+    let channel = |eta: f64, k: f64| {
+        let cos2 = c * c;
+        let sin2 = 1.0 - cos2;
+        let (eta2, k2) = (eta * eta, k * k);
+
+        let t0 = eta2 - k2 - sin2;
+        let a2_plus_b2 = (t0 * t0 + 4.0 * eta2 * k2).sqrt();
+        let t1 = a2_plus_b2 + cos2;
+        let a = (0.5 * (a2_plus_b2 + t0)).max(0.0).sqrt();
+        let t2 = 2.0 * c * a;
+        let rs = (t1 - t2) / (t1 + t2);
+
+        let t3 = cos2 * a2_plus_b2 + sin2 * sin2;
+        let t4 = t2 * sin2;
+        let rp = rs * (t3 - t4) / (t3 + t4);
+        0.5 * (rp + rs)
+    };
+    return Color::new(
+        channel(eta.x(), k.x()),
+        channel(eta.y(), k.y()),
+        channel(eta.z(), k.z()),
+    );
+}
+
+pub fn fresnel_dielectric(cos_theta_i: f64, eta: f64) -> f64 {
+    // The unpolarized reflectance of light arriving at cos_theta_i to the normal, where eta
+    // is the index of refraction on the far side over that on the near side. All of it is
+    // reflected past the critical angle.
+    let cos_i = cos_theta_i.clamp(0.0, 1.0);
+    let sin2_t = (1.0 - cos_i * cos_i) / (eta * eta);
+    if sin2_t >= 1.0 {
+        return 1.0;
+    }
+    let cos_t = (1.0 - sin2_t).sqrt();
+    let rs = (cos_i - eta * cos_t) / (cos_i + eta * cos_t);
+    let rp = (eta * cos_i - cos_t) / (eta * cos_i + cos_t);
+    return 0.5 * (rs * rs + rp * rp);
+}
@@ -2,6 +2,7 @@ use crate::math::interval::Interval;
 use crate::math::ray::Ray;
 use crate::math::vec3::Point3;
 
+#[allow(clippy::upper_case_acronyms)]
 #[derive(Clone, Copy)]
 pub struct AABB {
     x: Interval,
@@ -25,7 +26,9 @@ impl AABB {
     }
 
     pub fn new(x: Interval, y: Interval, z: Interval) -> AABB {
-        AABB { x, y, z }
+        let mut bbox = AABB { x, y, z };
+        bbox.pad_to_minimums();
+        bbox
     }
 
     pub fn from_points(a: Point3, b: Point3) -> AABB {
@@ -44,7 +47,9 @@ impl AABB {
         } else {
             Interval::new(b[2], a[2])
         };
-        AABB { x, y, z }
+        let mut bbox = AABB { x, y, z };
+        bbox.pad_to_minimums();
+        bbox
     }
 
     pub fn from_boxes(box0: &AABB, box1: &AABB) -> AABB {
@@ -55,6 +60,14 @@ impl AABB {
         }
     }
 
+    pub fn from_overlap(box0: &AABB, box1: &AABB) -> AABB {
+        AABB {
+            x: Interval::from_overlap(&box0.x, &box1.x),
+            y: Interval::from_overlap(&box0.y, &box1.y),
+            z: Interval::from_overlap(&box0.z, &box1.z),
+        }
+    }
+
     pub fn axis_interval(&self, n: usize) -> Interval {
         return match n {
             1 => self.y,
@@ -64,11 +77,15 @@ impl AABB {
     }
 
     pub fn hit(&self, r: &Ray, ray_t: &Interval) -> bool {
-        let mut ray_t = ray_t.clone();
+        return self.clip(r, ray_t).is_some();
+    }
+
+    pub fn clip(&self, r: &Ray, ray_t: &Interval) -> Option<Interval> {
+        // Returns the part of `ray_t` for which the ray is inside the box, if any.
+        let mut ray_t = *ray_t;
         for axis in 0..3 {
-            let axis = axis as usize;
             let ax = self.axis_interval(axis);
-            let adinv = 1.0 / r.direction[axis as usize];
+            let adinv = 1.0 / r.direction[axis];
 
             let t0 = (ax.min - r.origin[axis]) * adinv;
             let t1 = (ax.max - r.origin[axis]) * adinv;
@@ -90,10 +107,10 @@ impl AABB {
             }
 
             if ray_t.max <= ray_t.min {
-                return false;
+                return None;
             }
         }
-        return true;
+        return Some(ray_t);
     }
 
     pub fn longest_axis(&self) -> i64 {
@@ -112,4 +129,29 @@ impl AABB {
             }
         }
     }
+
+    pub fn is_bounded(&self) -> bool {
+        // Returns false if the box extends to infinity along any axis.
+        for axis in 0..3 {
+            let ax = self.axis_interval(axis);
+            if !ax.min.is_finite() || !ax.max.is_finite() {
+                return false;
+            }
+        }
+        return true;
+    }
+
+    fn pad_to_minimums(&mut self) {
+        // Adjust the AABB so that no side is narrower than some delta, padding if necessary.
+        let delta = 0.0001;
+        if self.x.size() < delta {
+            self.x = self.x.expand(delta);
+        }
+        if self.y.size() < delta {
+            self.y = self.y.expand(delta);
+        }
+        if self.z.size() < delta {
+            self.z = self.z.expand(delta);
+        }
+    }
 }
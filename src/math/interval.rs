@@ -10,6 +10,11 @@ impl Interval {
         max: f64::NEG_INFINITY,
     };
 
+    pub const UNIVERSE: Interval = Interval {
+        min: f64::NEG_INFINITY,
+        max: f64::INFINITY,
+    };
+
     pub fn blank() -> Interval {
         Interval {
             min: f64::INFINITY,
@@ -28,6 +33,13 @@ impl Interval {
         return Interval::new(min, max);
     }
 
+    pub fn from_overlap(a: &Interval, b: &Interval) -> Interval {
+        // Create the interval covered by both input intervals. It is empty if they are disjoint.
+        let min = if a.min >= b.min { a.min } else { b.min };
+        let max = if a.max <= b.max { a.max } else { b.max };
+        return Interval::new(min, max);
+    }
+
     pub fn size(&self) -> f64 {
         self.max - self.min
     }
@@ -0,0 +1,153 @@
+use std::ops::Mul;
+
+use crate::math::vec3::{Point3, Vec3};
+use crate::util;
+
+#[derive(Clone, Copy)]
+pub struct Mat4 {
+    pub m: [[f64; 4]; 4],
+}
+
+impl Mat4 {
+    pub const IDENTITY: Mat4 = Mat4 {
+        m: [
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ],
+    };
+
+    pub fn new(m: [[f64; 4]; 4]) -> Mat4 {
+        Mat4 { m }
+    }
+
+    pub fn translation(offset: Vec3) -> Mat4 {
+        let mut t = Mat4::IDENTITY;
+        t.m[0][3] = offset.x();
+        t.m[1][3] = offset.y();
+        t.m[2][3] = offset.z();
+        t
+    }
+
+    pub fn scaling(factors: Vec3) -> Mat4 {
+        let mut s = Mat4::IDENTITY;
+        s.m[0][0] = factors.x();
+        s.m[1][1] = factors.y();
+        s.m[2][2] = factors.z();
+        s
+    }
+
+    pub fn rotation(axis: Vec3, degrees: f64) -> Mat4 {
+        // Rotation by `degrees` counter-clockwise about `axis` (Rodrigues' formula).
+        let a = Vec3::unit_vector(axis);
+        let theta = util::degrees_to_radians(degrees);
+        let (sin_theta, cos_theta) = theta.sin_cos();
+        let k = 1.0 - cos_theta;
+        let (x, y, z) = (a.x(), a.y(), a.z());
+
+        Mat4::new([
+            [
+                cos_theta + x * x * k,
+                x * y * k - z * sin_theta,
+                x * z * k + y * sin_theta,
+                0.0,
+            ],
+            [
+                y * x * k + z * sin_theta,
+                cos_theta + y * y * k,
+                y * z * k - x * sin_theta,
+                0.0,
+            ],
+            [
+                z * x * k - y * sin_theta,
+                z * y * k + x * sin_theta,
+                cos_theta + z * z * k,
+                0.0,
+            ],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    pub fn transpose(&self) -> Mat4 {
+        let mut t = Mat4::IDENTITY;
+        for (i, row) in t.m.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                *value = self.m[j][i];
+            }
+        }
+        t
+    }
+
+    pub fn inverse(&self) -> Option<Mat4> {
+        // Gauss-Jordan elimination with partial pivoting. Returns None for singular matrices.
+        let mut a = self.m;
+        let mut inv = Mat4::IDENTITY.m;
+
+        for col in 0..4 {
+            let mut pivot = col;
+            for row in col + 1..4 {
+                if a[row][col].abs() > a[pivot][col].abs() {
+                    pivot = row;
+                }
+            }
+            if a[pivot][col].abs() < 1e-12 {
+                return None;
+            }
+            a.swap(col, pivot);
+            inv.swap(col, pivot);
+
+            let scale = 1.0 / a[col][col];
+            for j in 0..4 {
+                a[col][j] *= scale;
+                inv[col][j] *= scale;
+            }
+
+            for row in 0..4 {
+                if row == col {
+                    continue;
+                }
+                let factor = a[row][col];
+                for j in 0..4 {
+                    a[row][j] -= factor * a[col][j];
+                    inv[row][j] -= factor * inv[col][j];
+                }
+            }
+        }
+
+        return Some(Mat4::new(inv));
+    }
+
+    pub fn transform_point(&self, p: &Point3) -> Point3 {
+        let m = &self.m;
+        return Point3::new(
+            m[0][0] * p.x() + m[0][1] * p.y() + m[0][2] * p.z() + m[0][3],
+            m[1][0] * p.x() + m[1][1] * p.y() + m[1][2] * p.z() + m[1][3],
+            m[2][0] * p.x() + m[2][1] * p.y() + m[2][2] * p.z() + m[2][3],
+        );
+    }
+
+    pub fn transform_vector(&self, v: &Vec3) -> Vec3 {
+        // Vectors are directions, so they ignore the translation column.
+        let m = &self.m;
+        return Vec3::new(
+            m[0][0] * v.x() + m[0][1] * v.y() + m[0][2] * v.z(),
+            m[1][0] * v.x() + m[1][1] * v.y() + m[1][2] * v.z(),
+            m[2][0] * v.x() + m[2][1] * v.y() + m[2][2] * v.z(),
+        );
+    }
+}
+
+impl Mul<Mat4> for Mat4 {
+    type Output = Mat4;
+
+    fn mul(self, rhs: Mat4) -> Self::Output {
+        let mut product = [[0.0; 4]; 4];
+        for (i, row) in product.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                *value = (0..4).map(|k| self.m[i][k] * rhs.m[k][j]).sum();
+            }
+        }
+        Mat4::new(product)
+    }
+}
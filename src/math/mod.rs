@@ -1,4 +1,9 @@
 pub mod aabb;
 pub mod interval;
+pub mod mat4;
+pub mod onb;
+pub mod polynomial;
+pub mod quaternion;
 pub mod ray;
+pub mod transform;
 pub mod vec3;
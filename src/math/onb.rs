@@ -0,0 +1,52 @@
+use crate::math::vec3::Vec3;
+
+#[derive(Clone)]
+pub struct Onb {
+    axis: [Vec3; 3],
+}
+
+impl Onb {
+    pub fn new(n: &Vec3) -> Onb {
+        // Build an orthonormal basis whose w axis points along `n`.
+        let w = Vec3::unit_vector(*n);
+        let a = if w.x().abs() > 0.9 {
+            Vec3::new(0.0, 1.0, 0.0)
+        } else {
+            Vec3::new(1.0, 0.0, 0.0)
+        };
+        let v = Vec3::unit_vector(Vec3::cross(w, a));
+        let u = Vec3::cross(w, v);
+        Onb { axis: [u, v, w] }
+    }
+
+    pub fn from_tangent(n: &Vec3, t: &Vec3) -> Onb {
+        // Build an orthonormal basis whose w axis points along `n` and whose u axis is as
+        // close to `t` as it can be. Falls back to any u if `t` runs along `n`.
+        let w = Vec3::unit_vector(*n);
+        let u = *t - Vec3::dot(t, &w) * w;
+        if u.length_squared() < 1e-12 {
+            return Onb::new(n);
+        }
+        let u = Vec3::unit_vector(u);
+        let v = Vec3::cross(w, u);
+        Onb { axis: [u, v, w] }
+    }
+
+    pub fn w(&self) -> Vec3 {
+        self.axis[2]
+    }
+
+    pub fn transform(&self, v: &Vec3) -> Vec3 {
+        // Transform from basis coordinates to local space.
+        return (v[0] * self.axis[0]) + (v[1] * self.axis[1]) + (v[2] * self.axis[2]);
+    }
+
+    pub fn to_local(&self, v: &Vec3) -> Vec3 {
+        // Transform from local space to basis coordinates.
+        return Vec3::new(
+            Vec3::dot(v, &self.axis[0]),
+            Vec3::dot(v, &self.axis[1]),
+            Vec3::dot(v, &self.axis[2]),
+        );
+    }
+}
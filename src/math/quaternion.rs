@@ -0,0 +1,161 @@
+use std::ops::Mul;
+
+use crate::math::mat4::Mat4;
+use crate::math::vec3::Vec3;
+use crate::util;
+
+#[derive(Clone, Copy)]
+pub struct Quat {
+    pub w: f64,
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Quat {
+    pub const IDENTITY: Quat = Quat {
+        w: 1.0,
+        x: 0.0,
+        y: 0.0,
+        z: 0.0,
+    };
+
+    pub fn new(w: f64, x: f64, y: f64, z: f64) -> Quat {
+        Quat { w, x, y, z }
+    }
+
+    pub fn from_axis_angle(axis: Vec3, degrees: f64) -> Quat {
+        // Rotation by `degrees` counter-clockwise about `axis`.
+        let a = Vec3::unit_vector(axis);
+        let half = util::degrees_to_radians(degrees) / 2.0;
+        let s = half.sin();
+        return Quat::new(half.cos(), a.x() * s, a.y() * s, a.z() * s);
+    }
+
+    #[allow(dead_code)]
+    pub fn to_axis_angle(self) -> (Vec3, f64) {
+        // Returns the rotation axis and the angle in degrees. The identity maps to +X, 0.
+        let q = self.normalized();
+        let sin_half = (1.0 - q.w * q.w).max(0.0).sqrt();
+        let degrees = 2.0 * q.w.clamp(-1.0, 1.0).acos() * 180.0 / util::PI;
+        if sin_half < 1e-9 {
+            return (Vec3::new(1.0, 0.0, 0.0), 0.0);
+        }
+        return (Vec3::new(q.x, q.y, q.z) / sin_half, degrees);
+    }
+
+    pub fn from_euler(x_degrees: f64, y_degrees: f64, z_degrees: f64) -> Quat {
+        // Rotate about the fixed X axis first, then Y, then Z.
+        let qx = Quat::from_axis_angle(Vec3::new(1.0, 0.0, 0.0), x_degrees);
+        let qy = Quat::from_axis_angle(Vec3::new(0.0, 1.0, 0.0), y_degrees);
+        let qz = Quat::from_axis_angle(Vec3::new(0.0, 0.0, 1.0), z_degrees);
+        return qz * qy * qx;
+    }
+
+    #[allow(dead_code)]
+    pub fn to_euler(self) -> (f64, f64, f64) {
+        // Inverse of `from_euler`, returning (x, y, z) angles in degrees.
+        let m = self.to_mat4().m;
+        let x = f64::atan2(m[2][1], m[2][2]);
+        let y = (-m[2][0]).clamp(-1.0, 1.0).asin();
+        let z = f64::atan2(m[1][0], m[0][0]);
+        let to_degrees = 180.0 / util::PI;
+        return (x * to_degrees, y * to_degrees, z * to_degrees);
+    }
+
+    pub fn dot(a: &Quat, b: &Quat) -> f64 {
+        a.w * b.w + a.x * b.x + a.y * b.y + a.z * b.z
+    }
+
+    pub fn length(&self) -> f64 {
+        Quat::dot(self, self).sqrt()
+    }
+
+    pub fn normalized(&self) -> Quat {
+        let len = self.length();
+        return Quat::new(self.w / len, self.x / len, self.y / len, self.z / len);
+    }
+
+    pub fn conjugate(&self) -> Quat {
+        Quat::new(self.w, -self.x, -self.y, -self.z)
+    }
+
+    #[allow(dead_code)]
+    pub fn rotate(&self, v: &Vec3) -> Vec3 {
+        // Computes q v q* for a unit quaternion q.
+        let p = Quat::new(0.0, v.x(), v.y(), v.z());
+        let r = *self * p * self.conjugate();
+        return Vec3::new(r.x, r.y, r.z);
+    }
+
+    #[allow(dead_code)]
+    pub fn slerp(a: &Quat, b: &Quat, t: f64) -> Quat {
+        // Spherical linear interpolation along the shortest arc from `a` to `b`.
+        let mut b = *b;
+        let mut cos_theta = Quat::dot(a, &b);
+        if cos_theta < 0.0 {
+            b = Quat::new(-b.w, -b.x, -b.y, -b.z);
+            cos_theta = -cos_theta;
+        }
+
+        let (wa, wb) = if cos_theta > 0.9995 {
+            // Nearly parallel: fall back to a normalized linear blend.
+            (1.0 - t, t)
+        } else {
+            let theta = cos_theta.acos();
+            let sin_theta = theta.sin();
+            (
+                ((1.0 - t) * theta).sin() / sin_theta,
+                (t * theta).sin() / sin_theta,
+            )
+        };
+
+        return Quat::new(
+            wa * a.w + wb * b.w,
+            wa * a.x + wb * b.x,
+            wa * a.y + wb * b.y,
+            wa * a.z + wb * b.z,
+        )
+        .normalized();
+    }
+
+    pub fn to_mat4(self) -> Mat4 {
+        let q = self.normalized();
+        let (w, x, y, z) = (q.w, q.x, q.y, q.z);
+        Mat4::new([
+            [
+                1.0 - 2.0 * (y * y + z * z),
+                2.0 * (x * y - w * z),
+                2.0 * (x * z + w * y),
+                0.0,
+            ],
+            [
+                2.0 * (x * y + w * z),
+                1.0 - 2.0 * (x * x + z * z),
+                2.0 * (y * z - w * x),
+                0.0,
+            ],
+            [
+                2.0 * (x * z - w * y),
+                2.0 * (y * z + w * x),
+                1.0 - 2.0 * (x * x + y * y),
+                0.0,
+            ],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+}
+
+impl Mul<Quat> for Quat {
+    type Output = Quat;
+
+    fn mul(self, rhs: Quat) -> Self::Output {
+        // Hamilton product: applying the result rotates by `rhs` first, then `self`.
+        Quat::new(
+            self.w * rhs.w - self.x * rhs.x - self.y * rhs.y - self.z * rhs.z,
+            self.w * rhs.x + self.x * rhs.w + self.y * rhs.z - self.z * rhs.y,
+            self.w * rhs.y - self.x * rhs.z + self.y * rhs.w + self.z * rhs.x,
+            self.w * rhs.z + self.x * rhs.y - self.y * rhs.x + self.z * rhs.w,
+        )
+    }
+}
@@ -4,6 +4,17 @@ pub struct Ray {
     pub origin: Point3,
     pub direction: Vec3,
     pub time: f64,
+    pub differentials: Option<RayDifferentials>,
+}
+
+// Two neighbouring rays, offset by about a pixel's footprint in x and y on the image. They
+// tell texture lookups how much of the texture one sample covers.
+#[derive(Clone, Copy)]
+pub struct RayDifferentials {
+    pub rx_origin: Point3,
+    pub rx_direction: Vec3,
+    pub ry_origin: Point3,
+    pub ry_direction: Vec3,
 }
 
 impl Ray {
@@ -12,6 +23,7 @@ impl Ray {
             origin: Point3::blank(),
             direction: Vec3::blank(),
             time: 0.0,
+            differentials: None,
         }
     }
 
@@ -20,6 +32,7 @@ impl Ray {
             origin,
             direction,
             time: 0.0,
+            differentials: None,
         }
     }
 
@@ -28,6 +41,7 @@ impl Ray {
             origin,
             direction,
             time,
+            differentials: None,
         }
     }
 
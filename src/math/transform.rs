@@ -0,0 +1,180 @@
+use std::ops::Mul;
+
+use crate::math::aabb::AABB;
+use crate::math::interval::Interval;
+use crate::math::mat4::Mat4;
+use crate::math::quaternion::Quat;
+use crate::math::ray::{Ray, RayDifferentials};
+use crate::math::vec3::{Point3, Vec3};
+
+// Rays, boxes, vectors and other transforms support `*`. Since `Point3` is the same type as
+// `Vec3`, `*` can only mean one of them, so it transforms directions as vectors, ignoring the
+// translation. Points and normals go through point() and normal().
+#[derive(Clone, Copy)]
+pub struct Transform {
+    matrix: Mat4,
+    inverse: Mat4,
+    normal_matrix: Mat4, // Inverse transpose, for transforming surface normals
+}
+
+impl Transform {
+    #[allow(dead_code)]
+    pub const IDENTITY: Transform = Transform {
+        matrix: Mat4::IDENTITY,
+        inverse: Mat4::IDENTITY,
+        normal_matrix: Mat4::IDENTITY,
+    };
+
+    pub fn new(matrix: Mat4) -> Transform {
+        let inverse = matrix
+            .inverse()
+            .expect("transform matrix must be invertible");
+        Transform {
+            matrix,
+            inverse,
+            normal_matrix: inverse.transpose(),
+        }
+    }
+
+    pub fn translate(offset: Vec3) -> Transform {
+        Transform::new(Mat4::translation(offset))
+    }
+
+    pub fn rotate(axis: Vec3, degrees: f64) -> Transform {
+        Transform::new(Mat4::rotation(axis, degrees))
+    }
+
+    pub fn scale(factors: Vec3) -> Transform {
+        Transform::new(Mat4::scaling(factors))
+    }
+
+    pub fn from_quat(rotation: &Quat) -> Transform {
+        Transform::new(rotation.to_mat4())
+    }
+
+    pub fn from_trs(translation: Vec3, rotation: &Quat, scale: Vec3) -> Transform {
+        // Scale first, then rotate, then translate.
+        return Transform::translate(translation)
+            * Transform::from_quat(rotation)
+            * Transform::scale(scale);
+    }
+
+    #[allow(dead_code)]
+    pub fn look_at(eye: Point3, target: Point3, up: Vec3) -> Transform {
+        // Places an object at `eye` with its -Z axis facing `target` and +Y as close to `up`
+        // as possible, the same frame the camera builds from lookfrom/lookat/vup.
+        let w = Vec3::unit_vector(eye - target);
+        let u = Vec3::unit_vector(Vec3::cross(up, w));
+        let v = Vec3::cross(w, u);
+        return Transform::new(Mat4::new([
+            [u.x(), v.x(), w.x(), eye.x()],
+            [u.y(), v.y(), w.y(), eye.y()],
+            [u.z(), v.z(), w.z(), eye.z()],
+            [0.0, 0.0, 0.0, 1.0],
+        ]));
+    }
+
+    pub fn inverse(&self) -> Transform {
+        Transform {
+            matrix: self.inverse,
+            inverse: self.matrix,
+            normal_matrix: self.matrix.transpose(),
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn matrix(&self) -> &Mat4 {
+        &self.matrix
+    }
+
+    pub fn point(&self, p: &Point3) -> Point3 {
+        self.matrix.transform_point(p)
+    }
+
+    pub fn vector(&self, v: &Vec3) -> Vec3 {
+        self.matrix.transform_vector(v)
+    }
+
+    pub fn normal(&self, n: &Vec3) -> Vec3 {
+        // Normals use the inverse transpose so they stay perpendicular to the surface under
+        // non-uniform scaling. The result is not renormalized.
+        self.normal_matrix.transform_vector(n)
+    }
+
+    pub fn ray(&self, r: &Ray) -> Ray {
+        // The direction is left unnormalized so that ray parameters agree between spaces.
+        let mut transformed = Ray::new_timed(
+            self.point(&r.origin()),
+            self.vector(&r.direction()),
+            r.time(),
+        );
+        transformed.differentials = r.differentials.map(|d| RayDifferentials {
+            rx_origin: self.point(&d.rx_origin),
+            rx_direction: self.vector(&d.rx_direction),
+            ry_origin: self.point(&d.ry_origin),
+            ry_direction: self.vector(&d.ry_direction),
+        });
+        return transformed;
+    }
+
+    pub fn aabb(&self, bbox: &AABB) -> AABB {
+        // Arvo's method: each world axis spans the translation plus, for each object axis,
+        // that axis's ends scaled by the matrix term joining them. Zero terms are skipped,
+        // so an infinite extent only reaches the world axes it turns into, rather than
+        // giving infinity times zero.
+        let m = &self.matrix.m;
+        let mut axes = [Interval::EMPTY; 3];
+        for (i, axis) in axes.iter_mut().enumerate() {
+            let (mut min, mut max) = (m[i][3], m[i][3]);
+            for (j, &scale) in m[i][..3].iter().enumerate() {
+                if scale == 0.0 {
+                    continue;
+                }
+                let extent = bbox.axis_interval(j);
+                let (a, b) = (scale * extent.min, scale * extent.max);
+                min += a.min(b);
+                max += a.max(b);
+            }
+            *axis = Interval::new(min, max);
+        }
+
+        return AABB::new(axes[0], axes[1], axes[2]);
+    }
+}
+
+impl Mul<Transform> for Transform {
+    type Output = Transform;
+
+    fn mul(self, rhs: Transform) -> Self::Output {
+        // Applying the result applies `rhs` first, then `self`.
+        Transform {
+            matrix: self.matrix * rhs.matrix,
+            inverse: rhs.inverse * self.inverse,
+            normal_matrix: self.normal_matrix * rhs.normal_matrix,
+        }
+    }
+}
+
+impl Mul<&Ray> for &Transform {
+    type Output = Ray;
+
+    fn mul(self, rhs: &Ray) -> Self::Output {
+        self.ray(rhs)
+    }
+}
+
+impl Mul<&Vec3> for &Transform {
+    type Output = Vec3;
+
+    fn mul(self, rhs: &Vec3) -> Self::Output {
+        self.vector(rhs)
+    }
+}
+
+impl Mul<&AABB> for &Transform {
+    type Output = AABB;
+
+    fn mul(self, rhs: &AABB) -> Self::Output {
+        self.aabb(rhs)
+    }
+}
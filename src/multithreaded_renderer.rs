@@ -48,14 +48,10 @@ pub fn draw(height: usize, width: usize, receiver: Receiver<Pixel>) {
     // Main loop to keep the window open and responsive. This must be on the main thread.
     while window.is_open() && !window.is_key_down(Key::Escape) {
         {
-            let frame = frame_buffer.try_lock();
-            match frame {
-                Ok(frame) => {
-                    window
-                        .update_with_buffer(frame.as_slice(), width, height)
-                        .unwrap();
-                }
-                Err(_) => {}
+            if let Ok(frame) = frame_buffer.try_lock() {
+                window
+                    .update_with_buffer(frame.as_slice(), width, height)
+                    .unwrap();
             }
         }
         thread::sleep(Duration::from_millis(100));
@@ -0,0 +1,78 @@
+use std::sync::Arc;
+
+use crate::camera;
+use crate::color::Color;
+use crate::hittables::bvh::BvhNode;
+use crate::hittables::hittable_list::HittableList;
+use crate::hittables::metaballs::{Metaball, Metaballs};
+use crate::hittables::plane::Plane;
+use crate::material::material::{Dielectric, Lambertian, Metal};
+use crate::math::vec3::{Point3, Vec3};
+use crate::util;
+
+#[allow(dead_code)]
+pub fn render() {
+    // World
+    let mut world_list = HittableList::new();
+
+    let ground_material = Arc::new(Lambertian::new(Color::new(0.5, 0.5, 0.5)));
+    world_list.add(Arc::new(Plane::new(
+        Point3::new(0.0, 0.0, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+        ground_material,
+    )));
+
+    // A splash of water: a cluster of random blobs merging into one another.
+    let mut droplets = Vec::new();
+    for _ in 0..12 {
+        let center = Point3::new(
+            util::bounded_random_double(-2.6, -0.6),
+            util::bounded_random_double(0.6, 1.6),
+            util::bounded_random_double(-0.8, 0.8),
+        );
+        droplets.push(Metaball::new(
+            center,
+            util::bounded_random_double(0.5, 0.9),
+            1.0,
+        ));
+    }
+    world_list.add(Arc::new(Metaballs::new(
+        droplets,
+        0.3,
+        Arc::new(Dielectric::new(1.33)),
+    )));
+
+    // A chrome peanut with a dimple carved out by a negative ball.
+    let chrome = Arc::new(Metal::new(Color::new(0.8, 0.8, 0.85), 0.0));
+    world_list.add(Arc::new(Metaballs::new(
+        vec![
+            Metaball::new(Point3::new(1.3, 1.0, 0.0), 1.2, 1.0),
+            Metaball::new(Point3::new(2.3, 1.0, 0.0), 1.2, 1.0),
+            Metaball::new(Point3::new(1.8, 1.8, 0.6), 0.7, -1.0),
+        ],
+        0.4,
+        chrome,
+    )));
+
+    world_list = HittableList::single(Arc::new(BvhNode::from_hittable_list(&mut world_list)));
+
+    let world = Arc::new(world_list);
+
+    let camera_settings = camera::CameraSettings {
+        num_threads: 4,
+        aspect_ratio: 16.0 / 9.0,
+        image_width: 400,
+        samples_per_pixel: 100,
+        max_depth: 50,
+        vfov: 35.0,
+        lookfrom: Point3::new(0.0, 3.0, 8.0),
+        lookat: Point3::new(0.0, 1.0, 0.0),
+        vup: Vec3::new(0.0, 1.0, 0.0),
+        defocus_angle: 0.0,
+        focus_dist: 10.0,
+        background: camera::Background::sky(),
+    };
+
+    let cam = camera::Camera::initialize(camera_settings);
+    cam.render(world);
+}
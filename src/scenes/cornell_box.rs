@@ -0,0 +1,98 @@
+use std::sync::Arc;
+
+use crate::camera;
+use crate::color::Color;
+use crate::hittables::bvh::BvhNode;
+use crate::hittables::cuboid::Cuboid;
+use crate::hittables::hittable_list::HittableList;
+use crate::hittables::instance::Instance;
+use crate::material::material::{DiffuseLight, Lambertian};
+use crate::math::transform::Transform;
+use crate::math::vec3::{Point3, Vec3};
+
+#[allow(dead_code)]
+pub fn render() {
+    // World
+    let mut world_list = HittableList::new();
+
+    let red = Arc::new(Lambertian::new(Color::new(0.65, 0.05, 0.05)));
+    let white = Arc::new(Lambertian::new(Color::new(0.73, 0.73, 0.73)));
+    let green = Arc::new(Lambertian::new(Color::new(0.12, 0.45, 0.15)));
+    let light = Arc::new(DiffuseLight::new(Color::new(15.0, 15.0, 15.0)));
+
+    // The walls are thin slabs just outside the 555 unit box, open towards the camera.
+    let walls = [
+        (
+            Point3::new(555.0, 0.0, 0.0),
+            Point3::new(556.0, 555.0, 555.0),
+            green,
+        ),
+        (
+            Point3::new(-1.0, 0.0, 0.0),
+            Point3::new(0.0, 555.0, 555.0),
+            red,
+        ),
+    ];
+    for (a, b, mat) in walls {
+        world_list.add(Arc::new(Cuboid::new(a, b, mat)));
+    }
+    let white_walls = [
+        (Point3::new(0.0, -1.0, 0.0), Point3::new(555.0, 0.0, 555.0)),
+        (
+            Point3::new(0.0, 555.0, 0.0),
+            Point3::new(555.0, 556.0, 555.0),
+        ),
+        (
+            Point3::new(0.0, 0.0, 555.0),
+            Point3::new(555.0, 555.0, 556.0),
+        ),
+    ];
+    for (a, b) in white_walls {
+        world_list.add(Arc::new(Cuboid::new(a, b, white.clone())));
+    }
+
+    // The lamp hangs just below the ceiling.
+    world_list.add(Arc::new(Cuboid::new(
+        Point3::new(213.0, 554.0, 227.0),
+        Point3::new(343.0, 554.9, 332.0),
+        light,
+    )));
+
+    // A tall box and a short one, each turned a little about the vertical.
+    let boxes = [
+        (330.0, 15.0, Vec3::new(265.0, 0.0, 295.0)),
+        (165.0, -18.0, Vec3::new(130.0, 0.0, 65.0)),
+    ];
+    for (height, degrees, offset) in boxes {
+        let shape = Arc::new(Cuboid::new(
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(165.0, height, 165.0),
+            white.clone(),
+        ));
+        let transform =
+            Transform::translate(offset) * Transform::rotate(Vec3::new(0.0, 1.0, 0.0), degrees);
+        world_list.add(Arc::new(Instance::new(shape, transform)));
+    }
+
+    world_list = HittableList::single(Arc::new(BvhNode::from_hittable_list(&mut world_list)));
+
+    let world = Arc::new(world_list);
+
+    let camera_settings = camera::CameraSettings {
+        num_threads: 4,
+        aspect_ratio: 1.0,
+        image_width: 400,
+        samples_per_pixel: 200,
+        max_depth: 50,
+        vfov: 40.0,
+        lookfrom: Point3::new(278.0, 278.0, -800.0),
+        lookat: Point3::new(278.0, 278.0, 0.0),
+        vup: Vec3::new(0.0, 1.0, 0.0),
+        defocus_angle: 0.0,
+        focus_dist: 10.0,
+        background: camera::Background::None,
+    };
+
+    let cam = camera::Camera::initialize(camera_settings);
+    cam.render(world);
+}
@@ -0,0 +1,73 @@
+use std::sync::Arc;
+
+use crate::camera;
+use crate::color::Color;
+use crate::hittables::bvh::BvhNode;
+use crate::hittables::csg::{CsgDifference, CsgIntersection, CsgUnion};
+use crate::hittables::cuboid::Cuboid;
+use crate::hittables::hittable_list::HittableList;
+use crate::hittables::plane::Plane;
+use crate::hittables::sphere::Sphere;
+use crate::material::material::{Dielectric, Lambertian, Metal};
+use crate::math::vec3::{Point3, Vec3};
+
+#[allow(dead_code)]
+pub fn render() {
+    // World
+    let mut world_list = HittableList::new();
+
+    let ground_material = Arc::new(Lambertian::new(Color::new(0.5, 0.5, 0.5)));
+    world_list.add(Arc::new(Plane::new(
+        Point3::new(0.0, 0.0, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+        ground_material,
+    )));
+
+    // A biconvex lens: the overlap of two large spheres.
+    let glass = Arc::new(Dielectric::new(1.5));
+    world_list.add(Arc::new(CsgIntersection::new(
+        Arc::new(Sphere::new(Point3::new(-4.1, 1.2, 0.0), 2.0, glass.clone())),
+        Arc::new(Sphere::new(Point3::new(-0.9, 1.2, 0.0), 2.0, glass)),
+    )));
+
+    // A box with a spherical bite taken out of its corner, the cut painted a different color.
+    let red = Arc::new(Lambertian::new(Color::new(0.65, 0.05, 0.05)));
+    let white = Arc::new(Lambertian::new(Color::new(0.73, 0.73, 0.73)));
+    world_list.add(Arc::new(CsgDifference::new(
+        Arc::new(Cuboid::new(
+            Point3::new(-0.8, 0.0, -0.8),
+            Point3::new(0.8, 1.6, 0.8),
+            red,
+        )),
+        Arc::new(Sphere::new(Point3::new(0.8, 1.6, 0.8), 1.0, white)),
+    )));
+
+    // Two overlapping metal spheres merged into one solid.
+    let steel = Arc::new(Metal::new(Color::new(0.7, 0.7, 0.75), 0.05));
+    world_list.add(Arc::new(CsgUnion::new(
+        Arc::new(Sphere::new(Point3::new(2.3, 0.8, 0.0), 0.8, steel.clone())),
+        Arc::new(Sphere::new(Point3::new(3.0, 1.3, 0.0), 0.6, steel)),
+    )));
+
+    world_list = HittableList::single(Arc::new(BvhNode::from_hittable_list(&mut world_list)));
+
+    let world = Arc::new(world_list);
+
+    let camera_settings = camera::CameraSettings {
+        num_threads: 4,
+        aspect_ratio: 16.0 / 9.0,
+        image_width: 400,
+        samples_per_pixel: 100,
+        max_depth: 50,
+        vfov: 30.0,
+        lookfrom: Point3::new(3.0, 4.0, 10.0),
+        lookat: Point3::new(0.0, 0.8, 0.0),
+        vup: Vec3::new(0.0, 1.0, 0.0),
+        defocus_angle: 0.0,
+        focus_dist: 10.0,
+        background: camera::Background::sky(),
+    };
+
+    let cam = camera::Camera::initialize(camera_settings);
+    cam.render(world);
+}
@@ -0,0 +1,80 @@
+use std::sync::Arc;
+
+use crate::camera;
+use crate::color::Color;
+use crate::hittables::bvh::BvhNode;
+use crate::hittables::curve::{Curve, CurveShape};
+use crate::hittables::hittable_list::HittableList;
+use crate::hittables::plane::Plane;
+use crate::material::material::{Lambertian, Metal};
+use crate::math::vec3::{Point3, Vec3};
+use crate::util;
+
+#[allow(dead_code)]
+pub fn render() {
+    // World
+    let mut world_list = HittableList::new();
+
+    let ground_material = Arc::new(Lambertian::new(Color::new(0.4, 0.3, 0.2)));
+    world_list.add(Arc::new(Plane::new(
+        Point3::new(0.0, 0.0, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+        ground_material,
+    )));
+
+    // A lawn of tapering grass blades, each bending off in a random direction.
+    let grass = Arc::new(Lambertian::new(Color::new(0.2, 0.5, 0.1)));
+    for _ in 0..20000 {
+        let root = Point3::new(
+            util::bounded_random_double(-4.0, 4.0),
+            0.0,
+            util::bounded_random_double(-4.0, 2.0),
+        );
+        let height = util::bounded_random_double(0.3, 0.6);
+        let lean = util::bounded_random_double(0.1, 0.3) * Vec3::random_in_unit_disk();
+        let lean = Vec3::new(lean.x(), 0.0, lean.y());
+        let cp = [
+            root,
+            root + Vec3::new(0.0, 0.4 * height, 0.0),
+            root + 0.5 * lean + Vec3::new(0.0, 0.8 * height, 0.0),
+            root + lean + Vec3::new(0.0, height, 0.0),
+        ];
+        for piece in Curve::create(cp, 0.02, 0.0, CurveShape::Ribbon, grass.clone(), 2) {
+            world_list.add(piece);
+        }
+    }
+
+    // A thick copper tube arching over the lawn.
+    let copper = Arc::new(Metal::new(Color::new(0.8, 0.45, 0.3), 0.2));
+    let arch = [
+        Point3::new(-2.5, 0.0, -1.0),
+        Point3::new(-1.5, 2.5, -1.0),
+        Point3::new(1.5, 2.5, -1.0),
+        Point3::new(2.5, 0.0, -1.0),
+    ];
+    for piece in Curve::create(arch, 0.3, 0.1, CurveShape::Cylinder, copper, 8) {
+        world_list.add(piece);
+    }
+
+    world_list = HittableList::single(Arc::new(BvhNode::from_hittable_list(&mut world_list)));
+
+    let world = Arc::new(world_list);
+
+    let camera_settings = camera::CameraSettings {
+        num_threads: 4,
+        aspect_ratio: 16.0 / 9.0,
+        image_width: 400,
+        samples_per_pixel: 100,
+        max_depth: 50,
+        vfov: 35.0,
+        lookfrom: Point3::new(0.0, 1.5, 6.0),
+        lookat: Point3::new(0.0, 0.6, 0.0),
+        vup: Vec3::new(0.0, 1.0, 0.0),
+        defocus_angle: 0.0,
+        focus_dist: 10.0,
+        background: camera::Background::sky(),
+    };
+
+    let cam = camera::Camera::initialize(camera_settings);
+    cam.render(world);
+}
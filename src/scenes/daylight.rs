@@ -0,0 +1,70 @@
+use std::sync::Arc;
+
+use crate::camera;
+use crate::color::Color;
+use crate::hittables::bvh::BvhNode;
+use crate::hittables::cuboid::Cuboid;
+use crate::hittables::hittable_list::HittableList;
+use crate::hittables::plane::Plane;
+use crate::hittables::sphere::Sphere;
+use crate::material::material::{Dielectric, Lambertian, Metal};
+use crate::math::vec3::{Point3, Vec3};
+use crate::texture::sky::PhysicalSky;
+
+#[allow(dead_code)]
+pub fn render() {
+    // World
+    let mut world_list = HittableList::new();
+
+    world_list.add(Arc::new(Plane::new(
+        Point3::new(0.0, 0.0, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+        Arc::new(Lambertian::new(Color::new(0.5, 0.45, 0.4))),
+    )));
+
+    world_list.add(Arc::new(Sphere::new(
+        Point3::new(-2.2, 1.0, 0.0),
+        1.0,
+        Arc::new(Lambertian::new(Color::new(0.8, 0.8, 0.8))),
+    )));
+    world_list.add(Arc::new(Sphere::new(
+        Point3::new(0.0, 1.0, 0.0),
+        1.0,
+        Arc::new(Metal::new(Color::new(0.9, 0.9, 0.9), 0.0)),
+    )));
+    world_list.add(Arc::new(Sphere::new(
+        Point3::new(2.2, 1.0, 0.0),
+        1.0,
+        Arc::new(Dielectric::new(1.5)),
+    )));
+    world_list.add(Arc::new(Cuboid::new(
+        Point3::new(-1.0, 0.0, -3.5),
+        Point3::new(1.0, 2.5, -2.5),
+        Arc::new(Lambertian::new(Color::new(0.7, 0.3, 0.2))),
+    )));
+
+    world_list = HittableList::single(Arc::new(BvhNode::from_hittable_list(&mut world_list)));
+
+    let world = Arc::new(world_list);
+
+    // A late afternoon sun behind the camera's left shoulder, in a lightly hazy sky.
+    let sky = PhysicalSky::new(25.0, -130.0, 3.0, 0.05);
+
+    let camera_settings = camera::CameraSettings {
+        num_threads: 4,
+        aspect_ratio: 16.0 / 9.0,
+        image_width: 400,
+        samples_per_pixel: 100,
+        max_depth: 50,
+        vfov: 35.0,
+        lookfrom: Point3::new(0.0, 2.5, 9.0),
+        lookat: Point3::new(0.0, 1.6, 0.0),
+        vup: Vec3::new(0.0, 1.0, 0.0),
+        defocus_angle: 0.0,
+        focus_dist: 10.0,
+        background: camera::Background::PhysicalSky(Arc::new(sky)),
+    };
+
+    let cam = camera::Camera::initialize(camera_settings);
+    cam.render(world);
+}
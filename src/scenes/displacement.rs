@@ -0,0 +1,68 @@
+use std::sync::Arc;
+
+use crate::camera;
+use crate::color::Color;
+use crate::hittables::bvh::BvhNode;
+use crate::hittables::hittable_list::HittableList;
+use crate::hittables::plane::Plane;
+use crate::material::material::{Lambertian, Metal};
+use crate::math::vec3::{Point3, Vec3};
+use crate::scenes::subdivision::{cube_cage, octahedron_cage};
+use crate::texture::texture::{NoiseTexture, UvCheckerTexture};
+
+#[allow(dead_code)]
+pub fn render() {
+    // World
+    let mut world_list = HittableList::new();
+
+    let ground_material = Arc::new(Lambertian::new(Color::new(0.5, 0.5, 0.5)));
+    world_list.add(Arc::new(Plane::new(
+        Point3::new(0.0, 0.0, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+        ground_material,
+    )));
+
+    // A lumpy stone, displaced by noise.
+    let bumps = Arc::new(NoiseTexture::new(7, 1.5));
+    let ball = octahedron_cage(Point3::new(-1.4, 1.0, 0.0), 2.0)
+        .subdivide(3)
+        .displace(0.03, bumps, 0.3);
+    let stone = Arc::new(Lambertian::new(Color::new(0.6, 0.55, 0.45)));
+    world_list.add(Arc::new(ball.build(stone)));
+
+    // A smoothed cube with grooves running across each face, displaced by stripes in its
+    // texture coordinates.
+    let grooves = Arc::new(UvCheckerTexture::from_colors(
+        8.0,
+        1.0,
+        Color::new(1.0, 1.0, 1.0),
+        Color::new(0.0, 0.0, 0.0),
+    ));
+    let cube = cube_cage(Point3::new(1.4, 1.0, 0.0), 1.8)
+        .subdivide(2)
+        .displace(0.03, grooves, 0.1);
+    let brass = Arc::new(Metal::new(Color::new(0.8, 0.65, 0.3), 0.2));
+    world_list.add(Arc::new(cube.build(brass)));
+
+    world_list = HittableList::single(Arc::new(BvhNode::from_hittable_list(&mut world_list)));
+
+    let world = Arc::new(world_list);
+
+    let camera_settings = camera::CameraSettings {
+        num_threads: 4,
+        aspect_ratio: 16.0 / 9.0,
+        image_width: 400,
+        samples_per_pixel: 100,
+        max_depth: 50,
+        vfov: 30.0,
+        lookfrom: Point3::new(0.0, 3.5, 8.0),
+        lookat: Point3::new(0.0, 0.9, 0.0),
+        vup: Vec3::new(0.0, 1.0, 0.0),
+        defocus_angle: 0.0,
+        focus_dist: 10.0,
+        background: camera::Background::sky(),
+    };
+
+    let cam = camera::Camera::initialize(camera_settings);
+    cam.render(world);
+}
@@ -0,0 +1,80 @@
+use std::io;
+use std::sync::Arc;
+
+use crate::camera;
+use crate::color::Color;
+use crate::hittables::bvh::BvhNode;
+use crate::hittables::hittable_list::HittableList;
+use crate::hittables::plane::Plane;
+use crate::hittables::sphere::Sphere;
+use crate::material::material::{Dielectric, Lambertian, Metal};
+use crate::math::vec3::{Point3, Vec3};
+use crate::texture::environment::EnvironmentMap;
+use crate::texture::texture::UvCheckerTexture;
+
+#[allow(dead_code)]
+pub fn render() {
+    // World
+    let mut world_list = HittableList::new();
+
+    let ground_checker = Arc::new(UvCheckerTexture::from_colors(
+        2.0,
+        2.0,
+        Color::new(0.3, 0.3, 0.3),
+        Color::new(0.7, 0.7, 0.7),
+    ));
+    world_list.add(Arc::new(Plane::new(
+        Point3::new(0.0, 0.0, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+        Arc::new(Lambertian::from_texture(ground_checker)),
+    )));
+
+    world_list.add(Arc::new(Sphere::new(
+        Point3::new(-2.2, 1.0, 0.0),
+        1.0,
+        Arc::new(Lambertian::new(Color::new(0.8, 0.8, 0.8))),
+    )));
+    world_list.add(Arc::new(Sphere::new(
+        Point3::new(0.0, 1.0, 0.0),
+        1.0,
+        Arc::new(Metal::new(Color::new(0.9, 0.9, 0.9), 0.0)),
+    )));
+    world_list.add(Arc::new(Sphere::new(
+        Point3::new(2.2, 1.0, 0.0),
+        1.0,
+        Arc::new(Dielectric::new(1.5)),
+    )));
+
+    world_list = HittableList::single(Arc::new(BvhNode::from_hittable_list(&mut world_list)));
+
+    let world = Arc::new(world_list);
+
+    // The scene is lit by the sky, or by environment.hdr turned 120 degrees about the
+    // vertical if an images directory has one.
+    let background = match EnvironmentMap::load("environment.hdr", 120.0, 1.0) {
+        Ok(map) => camera::Background::Environment(Arc::new(map)),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => camera::Background::sky(),
+        Err(err) => {
+            eprintln!("ERROR: Could not load environment map: {}", err);
+            camera::Background::sky()
+        }
+    };
+
+    let camera_settings = camera::CameraSettings {
+        num_threads: 4,
+        aspect_ratio: 16.0 / 9.0,
+        image_width: 400,
+        samples_per_pixel: 100,
+        max_depth: 50,
+        vfov: 35.0,
+        lookfrom: Point3::new(0.0, 2.5, 9.0),
+        lookat: Point3::new(0.0, 1.0, 0.0),
+        vup: Vec3::new(0.0, 1.0, 0.0),
+        defocus_angle: 0.0,
+        focus_dist: 10.0,
+        background,
+    };
+
+    let cam = camera::Camera::initialize(camera_settings);
+    cam.render(world);
+}
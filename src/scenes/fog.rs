@@ -0,0 +1,92 @@
+use std::sync::Arc;
+
+use crate::camera;
+use crate::color::Color;
+use crate::hittables::bvh::BvhNode;
+use crate::hittables::constant_medium::ConstantMedium;
+use crate::hittables::cuboid::Cuboid;
+use crate::hittables::hittable_list::HittableList;
+use crate::hittables::plane::Plane;
+use crate::hittables::sphere::Sphere;
+use crate::material::material::{Dielectric, Lambertian, Metal};
+use crate::math::vec3::{Point3, Vec3};
+
+#[allow(dead_code)]
+pub fn render() {
+    // World
+    let mut world_list = HittableList::new();
+
+    let ground_material = Arc::new(Lambertian::new(Color::new(0.5, 0.5, 0.5)));
+    world_list.add(Arc::new(Plane::new(
+        Point3::new(0.0, 0.0, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+        ground_material,
+    )));
+
+    // A block of dark smoke.
+    let smoke_boundary = Arc::new(Cuboid::new(
+        Point3::new(-3.2, 0.0, -0.8),
+        Point3::new(-1.6, 2.0, 0.8),
+        Arc::new(Lambertian::new(Color::blank())),
+    ));
+    world_list.add(Arc::new(ConstantMedium::new(
+        smoke_boundary,
+        1.5,
+        Color::new(0.05, 0.05, 0.05),
+    )));
+
+    // A glass ball filled with blue haze, giving it a subsurface look.
+    let glass_center = Point3::new(0.0, 1.0, 0.0);
+    world_list.add(Arc::new(Sphere::new(
+        glass_center,
+        1.0,
+        Arc::new(Dielectric::new(1.5)),
+    )));
+    world_list.add(Arc::new(ConstantMedium::new(
+        Arc::new(Sphere::new(
+            glass_center,
+            0.99,
+            Arc::new(Dielectric::new(1.5)),
+        )),
+        2.0,
+        Color::new(0.2, 0.4, 0.9),
+    )));
+
+    // A metal ball half hidden in a bank of thin white mist.
+    world_list.add(Arc::new(Sphere::new(
+        Point3::new(2.4, 0.8, 0.0),
+        0.8,
+        Arc::new(Metal::new(Color::new(0.8, 0.6, 0.2), 0.0)),
+    )));
+    world_list.add(Arc::new(ConstantMedium::new(
+        Arc::new(Sphere::new(
+            Point3::new(2.4, 0.0, 0.5),
+            1.6,
+            Arc::new(Dielectric::new(1.5)),
+        )),
+        0.8,
+        Color::new(1.0, 1.0, 1.0),
+    )));
+
+    world_list = HittableList::single(Arc::new(BvhNode::from_hittable_list(&mut world_list)));
+
+    let world = Arc::new(world_list);
+
+    let camera_settings = camera::CameraSettings {
+        num_threads: 4,
+        aspect_ratio: 16.0 / 9.0,
+        image_width: 400,
+        samples_per_pixel: 100,
+        max_depth: 50,
+        vfov: 35.0,
+        lookfrom: Point3::new(0.0, 3.0, 8.0),
+        lookat: Point3::new(0.0, 0.8, 0.0),
+        vup: Vec3::new(0.0, 1.0, 0.0),
+        defocus_angle: 0.0,
+        focus_dist: 10.0,
+        background: camera::Background::sky(),
+    };
+
+    let cam = camera::Camera::initialize(camera_settings);
+    cam.render(world);
+}
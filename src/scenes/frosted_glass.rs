@@ -0,0 +1,85 @@
+use std::sync::Arc;
+
+use crate::camera;
+use crate::color::Color;
+use crate::hittables::bvh::BvhNode;
+use crate::hittables::cuboid::Cuboid;
+use crate::hittables::hittable_list::HittableList;
+use crate::hittables::plane::Plane;
+use crate::hittables::sphere::Sphere;
+use crate::material::material::{Lambertian, RoughDielectric};
+use crate::math::vec3::{Point3, Vec3};
+use crate::texture::sky::PhysicalSky;
+use crate::texture::texture::UvCheckerTexture;
+
+#[allow(dead_code)]
+pub fn render() {
+    // World
+    let mut world_list = HittableList::new();
+
+    world_list.add(Arc::new(Plane::new(
+        Point3::new(0.0, 0.0, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+        Arc::new(Lambertian::new(Color::new(0.5, 0.5, 0.5))),
+    )));
+
+    // A checkered wall and a row of colored posts behind the glass show how much each
+    // surface blurs what's behind it.
+    let wall_checker = Arc::new(UvCheckerTexture::from_colors(
+        4.0,
+        4.0,
+        Color::new(0.1, 0.1, 0.1),
+        Color::new(0.9, 0.9, 0.9),
+    ));
+    world_list.add(Arc::new(Plane::new(
+        Point3::new(0.0, 0.0, -3.0),
+        Vec3::new(0.0, 0.0, 1.0),
+        Arc::new(Lambertian::from_texture(wall_checker)),
+    )));
+
+    let posts = [
+        Color::new(0.8, 0.1, 0.1),
+        Color::new(0.1, 0.7, 0.1),
+        Color::new(0.1, 0.2, 0.8),
+        Color::new(0.8, 0.7, 0.1),
+    ];
+    for (i, (color, roughness)) in posts.into_iter().zip([0.0, 0.1, 0.25, 0.5]).enumerate() {
+        let x = -3.3 + 2.2 * i as f64;
+        world_list.add(Arc::new(Cuboid::new(
+            Point3::new(x - 0.15, 0.0, -2.0),
+            Point3::new(x + 0.15, 2.2, -1.7),
+            Arc::new(Lambertian::new(color)),
+        )));
+
+        // From clear glass on the left to heavily frosted on the right.
+        world_list.add(Arc::new(Sphere::new(
+            Point3::new(x, 1.0, 0.0),
+            1.0,
+            Arc::new(RoughDielectric::new(1.5, roughness)),
+        )));
+    }
+
+    world_list = HittableList::single(Arc::new(BvhNode::from_hittable_list(&mut world_list)));
+
+    let world = Arc::new(world_list);
+
+    let sky = PhysicalSky::new(40.0, -150.0, 3.0, 0.05);
+
+    let camera_settings = camera::CameraSettings {
+        num_threads: 4,
+        aspect_ratio: 16.0 / 9.0,
+        image_width: 400,
+        samples_per_pixel: 100,
+        max_depth: 50,
+        vfov: 35.0,
+        lookfrom: Point3::new(0.0, 2.0, 10.0),
+        lookat: Point3::new(0.0, 1.0, 0.0),
+        vup: Vec3::new(0.0, 1.0, 0.0),
+        defocus_angle: 0.0,
+        focus_dist: 10.0,
+        background: camera::Background::PhysicalSky(Arc::new(sky)),
+    };
+
+    let cam = camera::Camera::initialize(camera_settings);
+    cam.render(world);
+}
@@ -0,0 +1,78 @@
+use std::sync::Arc;
+
+use crate::camera;
+use crate::color::Color;
+use crate::hittables::bvh::BvhNode;
+use crate::hittables::hittable::Hittable;
+use crate::hittables::hittable_list::HittableList;
+use crate::hittables::instance::Instance;
+use crate::hittables::plane::Plane;
+use crate::hittables::torus::Torus;
+use crate::material::material::{Lambertian, Metal};
+use crate::math::quaternion::Quat;
+use crate::math::transform::Transform;
+use crate::math::vec3::{Point3, Vec3};
+use crate::util;
+
+#[allow(dead_code)]
+pub fn render() {
+    // World
+    let mut world_list = HittableList::new();
+
+    let ground_material = Arc::new(Lambertian::new(Color::new(0.5, 0.5, 0.5)));
+    world_list.add(Arc::new(Plane::new(
+        Point3::new(0.0, 0.0, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+        ground_material,
+    )));
+
+    // A single torus, shared by every instance below.
+    let ring: Arc<dyn Hittable> = Arc::new(Torus::new(
+        Point3::new(0.0, 0.0, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+        0.3,
+        0.1,
+        Arc::new(Metal::new(Color::new(0.8, 0.6, 0.2), 0.2)),
+    ));
+
+    for a in -15..15 {
+        for b in -15..15 {
+            let position = Vec3::new(
+                a as f64 + 0.9 * util::random_double(),
+                0.4,
+                b as f64 + 0.9 * util::random_double(),
+            );
+            let rotation = Quat::from_euler(
+                util::bounded_random_double(0.0, 360.0),
+                util::bounded_random_double(0.0, 360.0),
+                util::bounded_random_double(0.0, 360.0),
+            );
+            let stretch = Vec3::new(util::bounded_random_double(0.7, 1.3), 1.0, 1.0);
+
+            let transform = Transform::from_trs(position, &rotation, stretch);
+            world_list.add(Arc::new(Instance::new(ring.clone(), transform)));
+        }
+    }
+
+    world_list = HittableList::single(Arc::new(BvhNode::from_hittable_list(&mut world_list)));
+
+    let world = Arc::new(world_list);
+
+    let camera_settings = camera::CameraSettings {
+        num_threads: 4,
+        aspect_ratio: 16.0 / 9.0,
+        image_width: 400,
+        samples_per_pixel: 100,
+        max_depth: 50,
+        vfov: 20.0,
+        lookfrom: Point3::new(13.0, 4.0, 3.0),
+        lookat: Point3::new(0.0, 0.0, 0.0),
+        vup: Vec3::new(0.0, 1.0, 0.0),
+        defocus_angle: 0.0,
+        focus_dist: 10.0,
+        background: camera::Background::sky(),
+    };
+
+    let cam = camera::Camera::initialize(camera_settings);
+    cam.render(world);
+}
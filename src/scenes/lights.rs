@@ -0,0 +1,88 @@
+use std::sync::Arc;
+
+use crate::camera;
+use crate::color::Color;
+use crate::hittables::bvh::BvhNode;
+use crate::hittables::disk::Disk;
+use crate::hittables::hittable_list::HittableList;
+use crate::hittables::plane::Plane;
+use crate::hittables::sphere::Sphere;
+use crate::material::material::{Dielectric, DiffuseLight, Lambertian, Metal};
+use crate::math::vec3::{Point3, Vec3};
+use crate::texture::texture::{MarbleTexture, UvCheckerTexture};
+
+#[allow(dead_code)]
+pub fn render() {
+    // World
+    let mut world_list = HittableList::new();
+
+    let ground_checker = Arc::new(UvCheckerTexture::from_colors(
+        2.0,
+        2.0,
+        Color::new(0.2, 0.2, 0.2),
+        Color::new(0.7, 0.7, 0.7),
+    ));
+    world_list.add(Arc::new(Plane::new(
+        Point3::new(0.0, 0.0, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+        Arc::new(Lambertian::from_texture(ground_checker)),
+    )));
+
+    let marble = Arc::new(MarbleTexture::new(
+        7,
+        4.0,
+        Color::new(0.9, 0.9, 0.85),
+        Color::new(0.2, 0.2, 0.3),
+    ));
+    world_list.add(Arc::new(Sphere::new(
+        Point3::new(-1.2, 1.0, 0.0),
+        1.0,
+        Arc::new(Lambertian::from_texture(marble)),
+    )));
+    world_list.add(Arc::new(Sphere::new(
+        Point3::new(1.2, 1.0, 0.0),
+        1.0,
+        Arc::new(Metal::new(Color::new(0.8, 0.7, 0.6), 0.05)),
+    )));
+    world_list.add(Arc::new(Sphere::new(
+        Point3::new(0.0, 0.5, 1.8),
+        0.5,
+        Arc::new(Dielectric::new(1.5)),
+    )));
+
+    // A warm panel overhead, and a small cool globe lamp off to the right.
+    world_list.add(Arc::new(Disk::new(
+        Point3::new(0.0, 4.0, 0.5),
+        Vec3::new(0.0, -1.0, 0.0),
+        1.2,
+        Arc::new(DiffuseLight::new(Color::new(6.0, 5.0, 3.5))),
+    )));
+    world_list.add(Arc::new(Sphere::new(
+        Point3::new(2.6, 0.4, 1.2),
+        0.4,
+        Arc::new(DiffuseLight::new(Color::new(2.0, 4.0, 8.0))),
+    )));
+
+    world_list = HittableList::single(Arc::new(BvhNode::from_hittable_list(&mut world_list)));
+
+    let world = Arc::new(world_list);
+
+    // A faint night sky, so the lamps do almost all the lighting.
+    let camera_settings = camera::CameraSettings {
+        num_threads: 4,
+        aspect_ratio: 16.0 / 9.0,
+        image_width: 400,
+        samples_per_pixel: 200,
+        max_depth: 50,
+        vfov: 35.0,
+        lookfrom: Point3::new(0.0, 2.5, 9.0),
+        lookat: Point3::new(0.0, 1.0, 0.0),
+        vup: Vec3::new(0.0, 1.0, 0.0),
+        defocus_angle: 0.0,
+        focus_dist: 10.0,
+        background: camera::Background::Constant(Color::new(0.01, 0.01, 0.02)),
+    };
+
+    let cam = camera::Camera::initialize(camera_settings);
+    cam.render(world);
+}
@@ -0,0 +1,78 @@
+use std::sync::Arc;
+
+use crate::camera;
+use crate::color::Color;
+use crate::hittables::bvh::BvhNode;
+use crate::hittables::hittable_list::HittableList;
+use crate::hittables::plane::Plane;
+use crate::hittables::sphere::Sphere;
+use crate::material::material::{Conductor, Lambertian};
+use crate::math::vec3::{Point3, Vec3};
+use crate::texture::sky::PhysicalSky;
+use crate::texture::texture::UvCheckerTexture;
+
+#[allow(dead_code)]
+pub fn render() {
+    // World
+    let mut world_list = HittableList::new();
+
+    let ground_checker = Arc::new(UvCheckerTexture::from_colors(
+        1.0,
+        1.0,
+        Color::new(0.2, 0.2, 0.2),
+        Color::new(0.6, 0.6, 0.6),
+    ));
+    world_list.add(Arc::new(Plane::new(
+        Point3::new(0.0, 0.0, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+        Arc::new(Lambertian::from_texture(ground_checker)),
+    )));
+
+    // Gold, copper, silver and aluminum along the back row, all equally rough.
+    let presets = [
+        Conductor::gold(0.3),
+        Conductor::copper(0.3),
+        Conductor::silver(0.3),
+        Conductor::aluminum(0.3),
+    ];
+    for (i, metal) in presets.into_iter().enumerate() {
+        world_list.add(Arc::new(Sphere::new(
+            Point3::new(-3.3 + 2.2 * i as f64, 1.0, -1.5),
+            1.0,
+            Arc::new(metal),
+        )));
+    }
+
+    // Gold from polished to rough along the front row.
+    for (i, roughness) in [0.0, 0.15, 0.4, 0.8].into_iter().enumerate() {
+        world_list.add(Arc::new(Sphere::new(
+            Point3::new(-2.7 + 1.8 * i as f64, 0.7, 1.2),
+            0.7,
+            Arc::new(Conductor::gold(roughness)),
+        )));
+    }
+
+    world_list = HittableList::single(Arc::new(BvhNode::from_hittable_list(&mut world_list)));
+
+    let world = Arc::new(world_list);
+
+    let sky = PhysicalSky::new(35.0, -140.0, 3.0, 0.05);
+
+    let camera_settings = camera::CameraSettings {
+        num_threads: 4,
+        aspect_ratio: 16.0 / 9.0,
+        image_width: 400,
+        samples_per_pixel: 100,
+        max_depth: 50,
+        vfov: 35.0,
+        lookfrom: Point3::new(0.0, 3.0, 10.0),
+        lookat: Point3::new(0.0, 0.8, 0.0),
+        vup: Vec3::new(0.0, 1.0, 0.0),
+        defocus_angle: 0.0,
+        focus_dist: 10.0,
+        background: camera::Background::PhysicalSky(Arc::new(sky)),
+    };
+
+    let cam = camera::Camera::initialize(camera_settings);
+    cam.render(world);
+}
@@ -0,0 +1,93 @@
+use std::sync::Arc;
+
+use crate::camera;
+use crate::color::Color;
+use crate::hittables::bvh::BvhNode;
+use crate::hittables::hittable_list::HittableList;
+use crate::hittables::mesh::{Face, Mesh};
+use crate::hittables::sphere::Sphere;
+use crate::material::material::{Lambertian, Metal};
+use crate::math::vec3::{Point3, Vec3};
+use crate::texture::image::Image;
+use crate::texture::texture::{AddressMode, Filter, ImageTexture};
+
+#[allow(dead_code)]
+pub fn render() {
+    // World
+    let mut world_list = HittableList::new();
+
+    // A small checker image with 8 pixel squares, built in place so the scene needs no files.
+    let size = 64;
+    let mut pixels = Vec::with_capacity(size * size);
+    for y in 0..size {
+        for x in 0..size {
+            if (x / 8 + y / 8) % 2 == 0 {
+                pixels.push(Color::new(0.9, 0.9, 0.9));
+            } else {
+                pixels.push(Color::new(0.1, 0.1, 0.1));
+            }
+        }
+    }
+    let checker = Arc::new(Image::new(size, size, pixels));
+
+    // The ground stretches far back, tiling the image every two units. The left half is
+    // point sampled and shimmers into moire toward the horizon; the right half picks mip
+    // levels from the ray differentials and fades smoothly to gray.
+    let halves = [(-60.0, Filter::Nearest), (0.0, Filter::Trilinear)];
+    for (x, filter) in halves {
+        let texture = Arc::new(ImageTexture::from_image(
+            checker.clone(),
+            filter,
+            AddressMode::Wrap,
+        ));
+        let (x0, x1, z0, z1) = (x, x + 60.0, -200.0, 10.0);
+        let ground = Mesh::new(
+            vec![
+                Point3::new(x0, 0.0, z1),
+                Point3::new(x1, 0.0, z1),
+                Point3::new(x1, 0.0, z0),
+                Point3::new(x0, 0.0, z0),
+            ],
+            vec![
+                (x0 / 2.0, -z1 / 2.0),
+                (x1 / 2.0, -z1 / 2.0),
+                (x1 / 2.0, -z0 / 2.0),
+                (x0 / 2.0, -z0 / 2.0),
+            ],
+            vec![Face::with_uvs(vec![0, 1, 2, 3], vec![0, 1, 2, 3])],
+        );
+        world_list.add(Arc::new(
+            ground.build(Arc::new(Lambertian::from_texture(texture))),
+        ));
+    }
+
+    // A mirror ball carries the differentials through its reflection, so the ground seen in
+    // it is filtered too.
+    world_list.add(Arc::new(Sphere::new(
+        Point3::new(0.0, 1.0, -4.0),
+        1.0,
+        Arc::new(Metal::new(Color::new(0.8, 0.8, 0.8), 0.0)),
+    )));
+
+    world_list = HittableList::single(Arc::new(BvhNode::from_hittable_list(&mut world_list)));
+
+    let world = Arc::new(world_list);
+
+    let camera_settings = camera::CameraSettings {
+        num_threads: 4,
+        aspect_ratio: 16.0 / 9.0,
+        image_width: 400,
+        samples_per_pixel: 100,
+        max_depth: 50,
+        vfov: 40.0,
+        lookfrom: Point3::new(0.0, 1.5, 4.0),
+        lookat: Point3::new(0.0, 0.8, -10.0),
+        vup: Vec3::new(0.0, 1.0, 0.0),
+        defocus_angle: 0.0,
+        focus_dist: 10.0,
+        background: camera::Background::sky(),
+    };
+
+    let cam = camera::Camera::initialize(camera_settings);
+    cam.render(world);
+}
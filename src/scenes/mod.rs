@@ -1,2 +1,26 @@
+pub mod blobs;
+pub mod cornell_box;
+pub mod csg;
+pub mod curves;
+pub mod daylight;
+pub mod displacement;
+pub mod environment;
+pub mod fog;
+pub mod frosted_glass;
+pub mod instances;
+pub mod lights;
+pub mod metals;
+pub mod mipmap;
 pub mod moving_spheres;
+pub mod noise;
 pub mod part1_final;
+pub mod patches;
+pub mod point_cloud;
+pub mod primitives;
+pub mod principled;
+pub mod sdf_shapes;
+pub mod subdivision;
+pub mod terrain;
+pub mod textures;
+pub mod volumes;
+pub mod voxels;
@@ -96,6 +96,7 @@ pub fn render() {
         vup: Vec3::new(0.0, 1.0, 0.0),
         defocus_angle: 0.6,
         focus_dist: 10.0,
+        background: camera::Background::sky(),
     };
 
     let cam = camera::Camera::initialize(camera_settings);
@@ -0,0 +1,93 @@
+use std::sync::Arc;
+
+use crate::camera;
+use crate::color::Color;
+use crate::hittables::bvh::BvhNode;
+use crate::hittables::cuboid::Cuboid;
+use crate::hittables::cylinder::Cylinder;
+use crate::hittables::hittable_list::HittableList;
+use crate::hittables::plane::Plane;
+use crate::hittables::sphere::Sphere;
+use crate::material::material::Lambertian;
+use crate::math::vec3::{Point3, Vec3};
+use crate::texture::texture::{MarbleTexture, NoiseTexture, WoodTexture, WorleyTexture};
+
+#[allow(dead_code)]
+pub fn render() {
+    // World
+    let mut world_list = HittableList::new();
+
+    // A cobbled floor.
+    let cobbles = Arc::new(WorleyTexture::new(
+        7,
+        2.0,
+        Color::new(0.45, 0.42, 0.38),
+        Color::new(0.1, 0.1, 0.1),
+    ));
+    world_list.add(Arc::new(Plane::new(
+        Point3::new(0.0, 0.0, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+        Arc::new(Lambertian::from_texture(cobbles)),
+    )));
+
+    let marble = Arc::new(MarbleTexture::new(
+        1,
+        4.0,
+        Color::new(0.9, 0.9, 0.88),
+        Color::new(0.2, 0.2, 0.25),
+    ));
+    world_list.add(Arc::new(Sphere::new(
+        Point3::new(-2.4, 1.0, 0.0),
+        1.0,
+        Arc::new(Lambertian::from_texture(marble)),
+    )));
+
+    let noise = Arc::new(NoiseTexture::new(2, 3.0));
+    world_list.add(Arc::new(Sphere::new(
+        Point3::new(2.4, 1.0, 0.0),
+        1.0,
+        Arc::new(Lambertian::from_texture(noise)),
+    )));
+
+    // A log standing on end at the origin, where the rings are centered, and a plank cut
+    // along the grain.
+    let wood = Arc::new(Lambertian::from_texture(Arc::new(WoodTexture::new(
+        3,
+        6.0,
+        Color::new(0.75, 0.55, 0.3),
+        Color::new(0.35, 0.2, 0.08),
+    ))));
+    world_list.add(Arc::new(Cylinder::new(
+        Point3::new(0.0, 0.0, 0.0),
+        Point3::new(0.0, 1.6, 0.0),
+        0.6,
+        wood.clone(),
+    )));
+    world_list.add(Arc::new(Cuboid::new(
+        Point3::new(-0.6, 0.0, 0.9),
+        Point3::new(0.6, 0.25, 1.5),
+        wood,
+    )));
+
+    world_list = HittableList::single(Arc::new(BvhNode::from_hittable_list(&mut world_list)));
+
+    let world = Arc::new(world_list);
+
+    let camera_settings = camera::CameraSettings {
+        num_threads: 4,
+        aspect_ratio: 16.0 / 9.0,
+        image_width: 400,
+        samples_per_pixel: 100,
+        max_depth: 50,
+        vfov: 35.0,
+        lookfrom: Point3::new(0.0, 3.0, 8.0),
+        lookat: Point3::new(0.0, 0.9, 0.0),
+        vup: Vec3::new(0.0, 1.0, 0.0),
+        defocus_angle: 0.0,
+        focus_dist: 10.0,
+        background: camera::Background::sky(),
+    };
+
+    let cam = camera::Camera::initialize(camera_settings);
+    cam.render(world);
+}
@@ -0,0 +1,95 @@
+use std::sync::Arc;
+
+use crate::camera;
+use crate::color::Color;
+use crate::hittables::bezier_patch::BezierPatch;
+use crate::hittables::bvh::BvhNode;
+use crate::hittables::hittable_list::HittableList;
+use crate::hittables::plane::Plane;
+use crate::material::material::{Lambertian, Metal};
+use crate::math::vec3::{Point3, Vec3};
+
+#[allow(dead_code)]
+pub fn render() {
+    // World
+    let mut world_list = HittableList::new();
+
+    let ground_material = Arc::new(Lambertian::new(Color::new(0.5, 0.5, 0.5)));
+    world_list.add(Arc::new(Plane::new(
+        Point3::new(0.0, 0.0, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+        ground_material,
+    )));
+
+    // A vase of revolution: a profile curve swept around the y axis by four quarter-circle
+    // arcs, laid out as a shared vertex list with 16 indices per patch.
+    let profile = [(0.6, 0.0), (1.4, 0.6), (0.2, 1.6), (0.55, 2.4)];
+    let k = 0.5523; // Places the arc's inner control points so it closely follows a circle
+    let mut arc = Vec::new();
+    for quadrant in 0..4 {
+        let a = quadrant as f64 * std::f64::consts::FRAC_PI_2;
+        let e0 = (a.cos(), a.sin());
+        let e1 = (-a.sin(), a.cos());
+        arc.push(e0);
+        arc.push((e0.0 + k * e1.0, e0.1 + k * e1.1));
+        arc.push((e1.0 + k * e0.0, e1.1 + k * e0.1));
+    }
+
+    let center = Point3::new(-1.3, 0.0, 0.0);
+    let mut vertices = Vec::new();
+    for (radius, height) in profile {
+        for (c, s) in arc.iter() {
+            vertices.push(center + Vec3::new(radius * c, height, radius * s));
+        }
+    }
+
+    let ring = arc.len();
+    let mut patches = Vec::new();
+    for quadrant in 0..4 {
+        let mut indices = [0; 16];
+        for i in 0..4 {
+            for j in 0..4 {
+                indices[4 * i + j] = i * ring + (3 * quadrant + j) % ring;
+            }
+        }
+        patches.push(indices);
+    }
+
+    let glaze = Arc::new(Lambertian::new(Color::new(0.2, 0.35, 0.6)));
+    for patch in BezierPatch::from_mesh(&vertices, &patches, glaze) {
+        world_list.add(patch);
+    }
+
+    // A rippled sheet of polished copper.
+    let mut cp = [[Point3::blank(); 4]; 4];
+    for (i, row) in cp.iter_mut().enumerate() {
+        for (j, point) in row.iter_mut().enumerate() {
+            let height = if (i + j) % 2 == 0 { 0.4 } else { 1.6 };
+            *point = Point3::new(0.6 + 0.7 * i as f64, height, -1.2 + 0.8 * j as f64);
+        }
+    }
+    let copper = Arc::new(Metal::new(Color::new(0.9, 0.6, 0.4), 0.05));
+    world_list.add(Arc::new(BezierPatch::new(cp, copper)));
+
+    world_list = HittableList::single(Arc::new(BvhNode::from_hittable_list(&mut world_list)));
+
+    let world = Arc::new(world_list);
+
+    let camera_settings = camera::CameraSettings {
+        num_threads: 4,
+        aspect_ratio: 16.0 / 9.0,
+        image_width: 400,
+        samples_per_pixel: 100,
+        max_depth: 50,
+        vfov: 35.0,
+        lookfrom: Point3::new(0.0, 3.5, 8.0),
+        lookat: Point3::new(0.0, 1.0, 0.0),
+        vup: Vec3::new(0.0, 1.0, 0.0),
+        defocus_angle: 0.0,
+        focus_dist: 10.0,
+        background: camera::Background::sky(),
+    };
+
+    let cam = camera::Camera::initialize(camera_settings);
+    cam.render(world);
+}
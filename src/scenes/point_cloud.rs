@@ -0,0 +1,95 @@
+use std::sync::Arc;
+
+use crate::camera;
+use crate::color::Color;
+use crate::hittables::bvh::BvhNode;
+use crate::hittables::hittable_list::HittableList;
+use crate::hittables::plane::Plane;
+use crate::hittables::point_cloud::{CloudPoint, PointCloud, PointColors, PointShape};
+use crate::material::material::Lambertian;
+use crate::math::vec3::{Point3, Vec3};
+use crate::util;
+
+#[allow(dead_code)]
+pub fn render() {
+    // World
+    let mut world_list = HittableList::new();
+
+    let ground_material = Arc::new(Lambertian::new(Color::new(0.5, 0.5, 0.5)));
+    world_list.add(Arc::new(Plane::new(
+        Point3::new(0.0, 0.0, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+        ground_material,
+    )));
+
+    // A scan of rolling hills: surfels scattered over a height function, facing along its
+    // normal and colored from grass to rock by height.
+    let height = |x: f64, z: f64| 0.6 + 0.4 * (1.3 * x).sin() * (0.9 * z).cos();
+    let mut hills = Vec::new();
+    for _ in 0..400000 {
+        let x = util::bounded_random_double(-4.0, 1.0);
+        let z = util::bounded_random_double(-4.0, 2.0);
+        let y = height(x, z);
+        let h = 1e-4;
+        let normal = Vec3::new(
+            height(x - h, z) - height(x + h, z),
+            2.0 * h,
+            height(x, z - h) - height(x, z + h),
+        );
+        let grass = Color::new(0.2, 0.5, 0.15);
+        let rock = Color::new(0.55, 0.5, 0.45);
+        let s = (y - 0.2) / 0.8;
+        hills.push(CloudPoint {
+            position: Point3::new(x, y, z),
+            normal,
+            color: (1.0 - s) * grass + s * rock,
+        });
+    }
+    let hills_material = Arc::new(Lambertian::from_texture(Arc::new(PointColors::new(&hills))));
+    world_list.add(Arc::new(PointCloud::new(
+        hills,
+        0.012,
+        PointShape::Disk,
+        hills_material,
+    )));
+
+    // A sphere sampled as a shell of tiny beads, colored by direction.
+    let mut beads = Vec::new();
+    for _ in 0..100000 {
+        let direction = Vec3::random_unit_vector();
+        beads.push(CloudPoint {
+            position: Point3::new(2.2, 1.2, 0.0) + 1.0 * direction,
+            normal: Vec3::blank(),
+            color: 0.5 * (direction + Color::new(1.0, 1.0, 1.0)),
+        });
+    }
+    let beads_material = Arc::new(Lambertian::from_texture(Arc::new(PointColors::new(&beads))));
+    world_list.add(Arc::new(PointCloud::new(
+        beads,
+        0.01,
+        PointShape::Sphere,
+        beads_material,
+    )));
+
+    world_list = HittableList::single(Arc::new(BvhNode::from_hittable_list(&mut world_list)));
+
+    let world = Arc::new(world_list);
+
+    let camera_settings = camera::CameraSettings {
+        num_threads: 4,
+        aspect_ratio: 16.0 / 9.0,
+        image_width: 400,
+        samples_per_pixel: 100,
+        max_depth: 50,
+        vfov: 35.0,
+        lookfrom: Point3::new(0.0, 3.5, 8.0),
+        lookat: Point3::new(0.0, 0.8, 0.0),
+        vup: Vec3::new(0.0, 1.0, 0.0),
+        defocus_angle: 0.0,
+        focus_dist: 10.0,
+        background: camera::Background::sky(),
+    };
+
+    let cam = camera::Camera::initialize(camera_settings);
+    cam.render(world);
+}
@@ -0,0 +1,89 @@
+use std::sync::Arc;
+
+use crate::camera;
+use crate::color::Color;
+use crate::hittables::bvh::BvhNode;
+use crate::hittables::cone::Cone;
+use crate::hittables::cylinder::Cylinder;
+use crate::hittables::disk::Disk;
+use crate::hittables::hittable_list::HittableList;
+use crate::hittables::plane::Plane;
+use crate::hittables::sphere::Sphere;
+use crate::hittables::torus::Torus;
+use crate::material::material::{Dielectric, Lambertian, Metal};
+use crate::math::vec3::{Point3, Vec3};
+
+#[allow(dead_code)]
+pub fn render() {
+    // World
+    let mut world_list = HittableList::new();
+
+    let ground_material = Arc::new(Lambertian::new(Color::new(0.5, 0.5, 0.5)));
+    world_list.add(Arc::new(Plane::new(
+        Point3::new(0.0, 0.0, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+        ground_material,
+    )));
+
+    let red = Arc::new(Lambertian::new(Color::new(0.65, 0.05, 0.05)));
+    world_list.add(Arc::new(Cylinder::new(
+        Point3::new(-3.0, 0.0, 0.0),
+        Point3::new(-3.0, 1.5, 0.0),
+        0.6,
+        red,
+    )));
+
+    let green = Arc::new(Lambertian::new(Color::new(0.12, 0.45, 0.15)));
+    world_list.add(Arc::new(Cone::new(
+        Point3::new(-1.0, 0.0, 0.0),
+        Point3::new(-1.0, 1.8, 0.0),
+        0.7,
+        green,
+    )));
+
+    let gold = Arc::new(Metal::new(Color::new(0.8, 0.6, 0.2), 0.1));
+    world_list.add(Arc::new(Torus::new(
+        Point3::new(1.0, 0.9, 0.0),
+        Vec3::new(0.0, 0.0, 1.0),
+        0.6,
+        0.25,
+        gold,
+    )));
+
+    let glass = Arc::new(Dielectric::new(1.5));
+    world_list.add(Arc::new(Sphere::new(
+        Point3::new(3.0, 0.8, 0.0),
+        0.8,
+        glass,
+    )));
+
+    let blue = Arc::new(Lambertian::new(Color::new(0.1, 0.2, 0.5)));
+    world_list.add(Arc::new(Disk::new(
+        Point3::new(0.0, 1.5, -3.0),
+        Vec3::new(0.0, 0.3, 1.0),
+        1.5,
+        blue,
+    )));
+
+    world_list = HittableList::single(Arc::new(BvhNode::from_hittable_list(&mut world_list)));
+
+    let world = Arc::new(world_list);
+
+    let camera_settings = camera::CameraSettings {
+        num_threads: 4,
+        aspect_ratio: 16.0 / 9.0,
+        image_width: 400,
+        samples_per_pixel: 100,
+        max_depth: 50,
+        vfov: 30.0,
+        lookfrom: Point3::new(0.0, 3.0, 10.0),
+        lookat: Point3::new(0.0, 0.8, 0.0),
+        vup: Vec3::new(0.0, 1.0, 0.0),
+        defocus_angle: 0.0,
+        focus_dist: 10.0,
+        background: camera::Background::sky(),
+    };
+
+    let cam = camera::Camera::initialize(camera_settings);
+    cam.render(world);
+}
@@ -0,0 +1,143 @@
+use std::sync::Arc;
+
+use crate::camera;
+use crate::color::Color;
+use crate::hittables::bvh::BvhNode;
+use crate::hittables::hittable_list::HittableList;
+use crate::hittables::plane::Plane;
+use crate::hittables::sphere::Sphere;
+use crate::material::material::{Lambertian, Principled};
+use crate::math::vec3::{Point3, Vec3};
+use crate::texture::sky::PhysicalSky;
+use crate::texture::texture::{
+    CheckerTexture, MarbleTexture, SolidColor, Texture, UvCheckerTexture,
+};
+
+#[allow(dead_code)]
+pub fn render() {
+    // World
+    let mut world_list = HittableList::new();
+
+    let ground_checker = Arc::new(UvCheckerTexture::from_colors(
+        1.0,
+        1.0,
+        Color::new(0.25, 0.25, 0.25),
+        Color::new(0.6, 0.6, 0.6),
+    ));
+    world_list.add(Arc::new(Plane::new(
+        Point3::new(0.0, 0.0, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+        Arc::new(Lambertian::from_texture(ground_checker)),
+    )));
+
+    let value = |v: f64| -> Arc<dyn Texture> { Arc::new(SolidColor::from_value(v)) };
+
+    // Back row: red plastic from glossy to rough, polished gold, and brushed steel.
+    let gold = Color::new(1.0, 0.78, 0.34);
+    let steel = Color::new(0.6, 0.6, 0.62);
+    let back: [Principled; 5] = [
+        Principled {
+            roughness: value(0.05),
+            ..Principled::new(Color::new(0.7, 0.05, 0.05))
+        },
+        Principled {
+            roughness: value(0.3),
+            ..Principled::new(Color::new(0.7, 0.05, 0.05))
+        },
+        Principled {
+            roughness: value(0.7),
+            ..Principled::new(Color::new(0.7, 0.05, 0.05))
+        },
+        Principled {
+            metallic: value(1.0),
+            roughness: value(0.2),
+            ..Principled::new(gold)
+        },
+        Principled {
+            metallic: value(1.0),
+            roughness: value(0.4),
+            anisotropic: value(0.9),
+            ..Principled::new(steel)
+        },
+    ];
+
+    // Front row: clearcoated car paint, velvet, clear and frosted green glass, and a marble
+    // ball inlaid with polished metal squares.
+    let marble = Arc::new(MarbleTexture::new(
+        3,
+        4.0,
+        Color::new(0.9, 0.9, 0.85),
+        Color::new(0.2, 0.25, 0.4),
+    ));
+    let inlay = Arc::new(CheckerTexture::from_colors(
+        0.25,
+        Color::new(0.0, 0.0, 0.0),
+        Color::new(1.0, 1.0, 1.0),
+    ));
+    let glass = Color::new(0.7, 1.0, 0.8);
+    let front: [Principled; 5] = [
+        Principled {
+            roughness: value(0.6),
+            clearcoat: value(1.0),
+            ..Principled::new(Color::new(0.05, 0.1, 0.5))
+        },
+        Principled {
+            roughness: value(1.0),
+            sheen: value(1.0),
+            ..Principled::new(Color::new(0.35, 0.05, 0.3))
+        },
+        Principled {
+            roughness: value(0.02),
+            transmission: value(1.0),
+            ..Principled::new(glass)
+        },
+        Principled {
+            roughness: value(0.35),
+            transmission: value(1.0),
+            ..Principled::new(glass)
+        },
+        Principled {
+            metallic: inlay.clone(),
+            roughness: Arc::new(CheckerTexture::from_colors(
+                0.25,
+                Color::new(0.5, 0.5, 0.5),
+                Color::new(0.1, 0.1, 0.1),
+            )),
+            ..Principled::from_texture(marble)
+        },
+    ];
+
+    for (z, radius, row) in [(-1.5, 0.8, back), (1.2, 0.8, front)] {
+        for (i, mat) in row.into_iter().enumerate() {
+            world_list.add(Arc::new(Sphere::new(
+                Point3::new(-3.6 + 1.8 * i as f64, radius, z),
+                radius,
+                Arc::new(mat),
+            )));
+        }
+    }
+
+    world_list = HittableList::single(Arc::new(BvhNode::from_hittable_list(&mut world_list)));
+
+    let world = Arc::new(world_list);
+
+    let sky = PhysicalSky::new(35.0, -140.0, 3.0, 0.05);
+
+    let camera_settings = camera::CameraSettings {
+        num_threads: 4,
+        aspect_ratio: 16.0 / 9.0,
+        image_width: 400,
+        samples_per_pixel: 100,
+        max_depth: 50,
+        vfov: 35.0,
+        lookfrom: Point3::new(0.0, 5.0, 10.0),
+        lookat: Point3::new(0.0, 0.6, 0.0),
+        vup: Vec3::new(0.0, 1.0, 0.0),
+        defocus_angle: 0.0,
+        focus_dist: 10.0,
+        background: camera::Background::PhysicalSky(Arc::new(sky)),
+    };
+
+    let cam = camera::Camera::initialize(camera_settings);
+    cam.render(world);
+}
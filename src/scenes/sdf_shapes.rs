@@ -0,0 +1,80 @@
+use std::sync::Arc;
+
+use crate::camera;
+use crate::color::Color;
+use crate::hittables::bvh::BvhNode;
+use crate::hittables::hittable_list::HittableList;
+use crate::hittables::plane::Plane;
+use crate::hittables::sdf::{self, SdfHittable};
+use crate::material::material::{Lambertian, Metal};
+use crate::math::aabb::AABB;
+use crate::math::vec3::{Point3, Vec3};
+
+#[allow(dead_code)]
+pub fn render() {
+    // World
+    let mut world_list = HittableList::new();
+
+    let ground_material = Arc::new(Lambertian::new(Color::new(0.5, 0.5, 0.5)));
+    world_list.add(Arc::new(Plane::new(
+        Point3::new(0.0, 0.0, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+        ground_material,
+    )));
+
+    // A rounded box.
+    let center = Point3::new(-2.5, 0.7, 0.0);
+    let half_extents = Vec3::new(0.7, 0.7, 0.7);
+    world_list.add(Arc::new(SdfHittable::new(
+        Arc::new(move |p: &Point3| sdf::round_box(&(*p - center), &half_extents, 0.2)),
+        AABB::from_points(center - half_extents, center + half_extents),
+        Arc::new(Lambertian::new(Color::new(0.65, 0.05, 0.05))),
+    )));
+
+    // A sphere smoothly blended into a torus.
+    let center = Point3::new(0.0, 0.8, 0.0);
+    let extent = Vec3::new(1.2, 1.2, 1.2);
+    world_list.add(Arc::new(SdfHittable::new(
+        Arc::new(move |p: &Point3| {
+            let q = *p - center;
+            let ball = sdf::sphere(&(q - Vec3::new(0.0, 0.4, 0.0)), 0.4);
+            let ring = sdf::torus(&q, 0.7, 0.2);
+            sdf::smooth_union(ball, ring, 0.3)
+        }),
+        AABB::from_points(center - extent, center + extent),
+        Arc::new(Metal::new(Color::new(0.7, 0.7, 0.75), 0.1)),
+    )));
+
+    // A Mandelbulb.
+    let center = Point3::new(2.6, 1.2, 0.0);
+    let extent = Vec3::new(1.2, 1.2, 1.2);
+    world_list.add(Arc::new(SdfHittable::new_with_precision(
+        Arc::new(move |p: &Point3| sdf::mandelbulb(&(*p - center), 8.0, 8)),
+        AABB::from_points(center - extent, center + extent),
+        Arc::new(Lambertian::new(Color::new(0.8, 0.6, 0.2))),
+        1e-4,
+        512,
+    )));
+
+    world_list = HittableList::single(Arc::new(BvhNode::from_hittable_list(&mut world_list)));
+
+    let world = Arc::new(world_list);
+
+    let camera_settings = camera::CameraSettings {
+        num_threads: 4,
+        aspect_ratio: 16.0 / 9.0,
+        image_width: 400,
+        samples_per_pixel: 100,
+        max_depth: 50,
+        vfov: 30.0,
+        lookfrom: Point3::new(0.0, 3.0, 10.0),
+        lookat: Point3::new(0.0, 1.0, 0.0),
+        vup: Vec3::new(0.0, 1.0, 0.0),
+        defocus_angle: 0.0,
+        focus_dist: 10.0,
+        background: camera::Background::sky(),
+    };
+
+    let cam = camera::Camera::initialize(camera_settings);
+    cam.render(world);
+}
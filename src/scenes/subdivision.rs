@@ -0,0 +1,119 @@
+use std::sync::Arc;
+
+use crate::camera;
+use crate::color::Color;
+use crate::hittables::bvh::BvhNode;
+use crate::hittables::hittable_list::HittableList;
+use crate::hittables::mesh::{Face, Mesh};
+use crate::hittables::plane::Plane;
+use crate::material::material::{Lambertian, Metal};
+use crate::math::vec3::{Point3, Vec3};
+
+pub fn cube_cage(center: Point3, size: f64) -> Mesh {
+    // Corner i sits at +x if bit 0 is set, +y for bit 1 and +z for bit 2.
+    let mut positions = Vec::new();
+    for i in 0..8 {
+        let offset = Vec3::new(
+            if i & 1 == 0 { -0.5 } else { 0.5 },
+            if i & 2 == 0 { -0.5 } else { 0.5 },
+            if i & 4 == 0 { -0.5 } else { 0.5 },
+        );
+        positions.push(center + size * offset);
+    }
+
+    let uvs = vec![(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)];
+    let faces = [
+        [0, 4, 6, 2],
+        [1, 3, 7, 5],
+        [0, 1, 5, 4],
+        [2, 6, 7, 3],
+        [0, 2, 3, 1],
+        [4, 5, 7, 6],
+    ]
+    .iter()
+    .map(|vertices| Face::with_uvs(vertices.to_vec(), vec![0, 1, 2, 3]))
+    .collect();
+
+    return Mesh::new(positions, uvs, faces);
+}
+
+pub fn octahedron_cage(center: Point3, size: f64) -> Mesh {
+    let positions = vec![
+        center + Vec3::new(size, 0.0, 0.0),
+        center + Vec3::new(-size, 0.0, 0.0),
+        center + Vec3::new(0.0, size, 0.0),
+        center + Vec3::new(0.0, -size, 0.0),
+        center + Vec3::new(0.0, 0.0, size),
+        center + Vec3::new(0.0, 0.0, -size),
+    ];
+
+    // One face per octant. Mirroring an odd number of axes flips the winding.
+    let mut faces = Vec::new();
+    for octant in 0..8 {
+        let x = octant & 1;
+        let y = 2 + ((octant >> 1) & 1);
+        let z = 4 + ((octant >> 2) & 1);
+        if (x + y + z) % 2 == 0 {
+            faces.push(Face::new(vec![x, y, z]));
+        } else {
+            faces.push(Face::new(vec![x, z, y]));
+        }
+    }
+
+    return Mesh::new(positions, Vec::new(), faces);
+}
+
+#[allow(dead_code)]
+pub fn render() {
+    // World
+    let mut world_list = HittableList::new();
+
+    let ground_material = Arc::new(Lambertian::new(Color::new(0.5, 0.5, 0.5)));
+    world_list.add(Arc::new(Plane::new(
+        Point3::new(0.0, 0.0, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+        ground_material,
+    )));
+
+    // The same cube cage three ways: as modelled, smoothed, and smoothed with its top rim
+    // creased so it stays sharp.
+    let clay = Arc::new(Lambertian::new(Color::new(0.7, 0.4, 0.3)));
+    let cage = cube_cage(Point3::new(-3.0, 0.75, 0.0), 1.5);
+    world_list.add(Arc::new(cage.build(clay.clone())));
+
+    let smooth = cube_cage(Point3::new(-1.0, 0.75, 0.0), 1.5).subdivide(4);
+    world_list.add(Arc::new(smooth.build(clay.clone())));
+
+    let mut creased = cube_cage(Point3::new(1.0, 0.75, 0.0), 1.5);
+    for (a, b) in [(2, 6), (6, 7), (7, 3), (3, 2)] {
+        creased.add_crease(a, b);
+    }
+    world_list.add(Arc::new(creased.subdivide(4).build(clay)));
+
+    // Loop subdivision rounds an octahedron towards a sphere about half its size.
+    let chrome = Arc::new(Metal::new(Color::new(0.8, 0.8, 0.85), 0.0));
+    let ball = octahedron_cage(Point3::new(3.0, 0.8, 0.0), 1.6).subdivide(4);
+    world_list.add(Arc::new(ball.build(chrome)));
+
+    world_list = HittableList::single(Arc::new(BvhNode::from_hittable_list(&mut world_list)));
+
+    let world = Arc::new(world_list);
+
+    let camera_settings = camera::CameraSettings {
+        num_threads: 4,
+        aspect_ratio: 16.0 / 9.0,
+        image_width: 400,
+        samples_per_pixel: 100,
+        max_depth: 50,
+        vfov: 35.0,
+        lookfrom: Point3::new(0.0, 4.0, 9.0),
+        lookat: Point3::new(0.0, 0.7, 0.0),
+        vup: Vec3::new(0.0, 1.0, 0.0),
+        defocus_angle: 0.0,
+        focus_dist: 10.0,
+        background: camera::Background::sky(),
+    };
+
+    let cam = camera::Camera::initialize(camera_settings);
+    cam.render(world);
+}
@@ -0,0 +1,70 @@
+use std::sync::Arc;
+
+use crate::camera;
+use crate::color::Color;
+use crate::hittables::bvh::BvhNode;
+use crate::hittables::heightfield::Heightfield;
+use crate::hittables::hittable_list::HittableList;
+use crate::hittables::sphere::Sphere;
+use crate::material::material::{Dielectric, Lambertian};
+use crate::math::vec3::{Point3, Vec3};
+
+#[allow(dead_code)]
+pub fn render() {
+    // World
+    let mut world_list = HittableList::new();
+
+    // Rolling hills from a few overlapping waves, sampled on a 256x256 grid.
+    let n = 256;
+    let mut heights = Vec::with_capacity(n * n);
+    for iz in 0..n {
+        for ix in 0..n {
+            let x = ix as f64 / (n - 1) as f64;
+            let z = iz as f64 / (n - 1) as f64;
+            let h = 0.5
+                + 0.25 * (6.0 * x).sin() * (5.0 * z).cos()
+                + 0.15 * (17.0 * x + 3.0 * z).sin()
+                + 0.05 * (41.0 * z - 13.0 * x).cos();
+            heights.push(h);
+        }
+    }
+
+    let ground_material = Arc::new(Lambertian::new(Color::new(0.35, 0.5, 0.25)));
+    world_list.add(Arc::new(Heightfield::new(
+        &heights,
+        n,
+        n,
+        Point3::new(-10.0, -1.0, -10.0),
+        Vec3::new(20.0, 2.5, 20.0),
+        ground_material,
+    )));
+
+    let glass = Arc::new(Dielectric::new(1.5));
+    world_list.add(Arc::new(Sphere::new(
+        Point3::new(0.0, 2.2, 0.0),
+        1.0,
+        glass,
+    )));
+
+    world_list = HittableList::single(Arc::new(BvhNode::from_hittable_list(&mut world_list)));
+
+    let world = Arc::new(world_list);
+
+    let camera_settings = camera::CameraSettings {
+        num_threads: 4,
+        aspect_ratio: 16.0 / 9.0,
+        image_width: 400,
+        samples_per_pixel: 100,
+        max_depth: 50,
+        vfov: 40.0,
+        lookfrom: Point3::new(9.0, 5.0, 9.0),
+        lookat: Point3::new(0.0, 0.5, 0.0),
+        vup: Vec3::new(0.0, 1.0, 0.0),
+        defocus_angle: 0.0,
+        focus_dist: 10.0,
+        background: camera::Background::sky(),
+    };
+
+    let cam = camera::Camera::initialize(camera_settings);
+    cam.render(world);
+}
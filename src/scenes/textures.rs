@@ -0,0 +1,116 @@
+use std::sync::Arc;
+
+use crate::camera;
+use crate::color::Color;
+use crate::hittables::bvh::BvhNode;
+use crate::hittables::hittable_list::HittableList;
+use crate::hittables::mesh::{Face, Mesh};
+use crate::hittables::plane::Plane;
+use crate::hittables::sphere::Sphere;
+use crate::material::material::{Lambertian, Metal};
+use crate::math::vec3::{Point3, Vec3};
+use crate::scenes::subdivision::cube_cage;
+use crate::texture::texture::{
+    AddressMode, CheckerTexture, Filter, ImageTexture, UvCheckerTexture,
+};
+
+#[allow(dead_code)]
+pub fn render() {
+    // World
+    let mut world_list = HittableList::new();
+
+    // The plane's texture coordinates repeat every unit, so this gives half-unit squares.
+    let ground_checker = Arc::new(UvCheckerTexture::from_colors(
+        2.0,
+        2.0,
+        Color::new(0.2, 0.3, 0.1),
+        Color::new(0.9, 0.9, 0.9),
+    ));
+    world_list.add(Arc::new(Plane::new(
+        Point3::new(0.0, 0.0, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+        Arc::new(Lambertian::from_texture(ground_checker)),
+    )));
+
+    // The globe looks for earthmap.jpg in an images directory, and shows up cyan without it.
+    let earth_texture = Arc::new(ImageTexture::new("earthmap.jpg"));
+    world_list.add(Arc::new(Sphere::new(
+        Point3::new(-2.2, 1.0, 0.0),
+        1.0,
+        Arc::new(Lambertian::from_texture(earth_texture)),
+    )));
+
+    // A solid checker fills space, so the sphere looks carved out of a block of cubes.
+    let solid_checker = Arc::new(CheckerTexture::from_colors(
+        0.3,
+        Color::new(0.8, 0.1, 0.1),
+        Color::new(0.9, 0.9, 0.9),
+    ));
+    world_list.add(Arc::new(Sphere::new(
+        Point3::new(0.0, 1.0, 0.0),
+        1.0,
+        Arc::new(Metal::from_texture(solid_checker, 0.1)),
+    )));
+
+    // Mesh texture coordinates follow the faces through subdivision.
+    let tile_checker = Arc::new(UvCheckerTexture::from_colors(
+        4.0,
+        4.0,
+        Color::new(0.1, 0.2, 0.6),
+        Color::new(0.9, 0.8, 0.2),
+    ));
+    let cube = cube_cage(Point3::new(2.2, 0.9, 0.0), 1.8).subdivide(3);
+    world_list.add(Arc::new(
+        cube.build(Arc::new(Lambertian::from_texture(tile_checker))),
+    ));
+
+    // Two walls behind, each with the map laid three times across: tiled pixel by pixel on
+    // the left, and mirrored with smooth filtering on the right.
+    let tiled = Arc::new(ImageTexture::with_sampling(
+        "earthmap.jpg",
+        Filter::Nearest,
+        AddressMode::Wrap,
+    ));
+    let mirrored = Arc::new(ImageTexture::with_sampling(
+        "earthmap.jpg",
+        Filter::Bilinear,
+        AddressMode::Mirror,
+    ));
+    for (x, texture) in [(-4.0, tiled), (0.0, mirrored)] {
+        let wall = Mesh::new(
+            vec![
+                Point3::new(x, 0.0, -3.0),
+                Point3::new(x + 4.0, 0.0, -3.0),
+                Point3::new(x + 4.0, 2.0, -3.0),
+                Point3::new(x, 2.0, -3.0),
+            ],
+            vec![(0.0, 0.0), (3.0, 0.0), (3.0, 1.5), (0.0, 1.5)],
+            vec![Face::with_uvs(vec![0, 1, 2, 3], vec![0, 1, 2, 3])],
+        );
+        world_list.add(Arc::new(
+            wall.build(Arc::new(Lambertian::from_texture(texture))),
+        ));
+    }
+
+    world_list = HittableList::single(Arc::new(BvhNode::from_hittable_list(&mut world_list)));
+
+    let world = Arc::new(world_list);
+
+    let camera_settings = camera::CameraSettings {
+        num_threads: 4,
+        aspect_ratio: 16.0 / 9.0,
+        image_width: 400,
+        samples_per_pixel: 100,
+        max_depth: 50,
+        vfov: 35.0,
+        lookfrom: Point3::new(0.0, 3.0, 8.0),
+        lookat: Point3::new(0.0, 0.9, 0.0),
+        vup: Vec3::new(0.0, 1.0, 0.0),
+        defocus_angle: 0.0,
+        focus_dist: 10.0,
+        background: camera::Background::sky(),
+    };
+
+    let cam = camera::Camera::initialize(camera_settings);
+    cam.render(world);
+}
@@ -0,0 +1,144 @@
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::camera;
+use crate::color::Color;
+use crate::hittables::bvh::BvhNode;
+use crate::hittables::grid_volume::{GridVolume, VolumeEmission, VolumeGrid};
+use crate::hittables::hittable_list::HittableList;
+use crate::hittables::plane::Plane;
+use crate::material::material::Lambertian;
+use crate::math::vec3::{Point3, Vec3};
+use crate::util;
+
+#[allow(dead_code)]
+pub fn render() {
+    // World
+    let mut world_list = HittableList::new();
+
+    let ground_material = Arc::new(Lambertian::new(Color::new(0.5, 0.5, 0.5)));
+    world_list.add(Arc::new(Plane::new(
+        Point3::new(0.0, 0.0, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+        ground_material,
+    )));
+
+    // A cumulus cloud of soft puffs. A grid file saved as cloud.raw, such as one baked from
+    // a simulation, takes its place.
+    let cloud_file = Path::new("cloud.raw");
+    let cloud = if cloud_file.exists() {
+        match VolumeGrid::load_raw(cloud_file) {
+            Ok(grid) => grid,
+            Err(err) => {
+                eprintln!("ERROR: Could not load volume grid: {}", err);
+                cumulus()
+            }
+        }
+    } else {
+        cumulus()
+    };
+    world_list.add(Arc::new(GridVolume::new(
+        cloud,
+        Point3::new(-4.0, 0.0, -2.0),
+        Vec3::new(4.0, 4.0, 4.0),
+        6.0,
+        Color::new(0.95, 0.95, 0.95),
+    )));
+
+    // A fireball whose core burns hotter than its sooty edge.
+    let lumps: Vec<(Vec3, f64)> = (0..12)
+        .map(|_| {
+            (
+                Vec3::random_unit_vector(),
+                util::bounded_random_double(0.05, 0.12),
+            )
+        })
+        .collect();
+    let shape = move |p: &Point3| {
+        let offset = *p - Point3::new(0.5, 0.5, 0.5);
+        let mut radius = 0.3;
+        for (direction, height) in lumps.iter() {
+            let along = Vec3::dot(direction, &Vec3::unit_vector(offset));
+            radius += height * along.max(0.0).powi(8);
+        }
+        return 1.0 - offset.length() / radius;
+    };
+    let fire_density = VolumeGrid::from_fn([64, 64, 64], |p| shape(p).clamp(0.0, 0.2) * 5.0);
+    let temperature = VolumeGrid::from_fn([64, 64, 64], |p| {
+        let core = shape(p);
+        if core <= 0.0 {
+            return 0.0;
+        }
+        return 1500.0 + 2000.0 * core.min(1.0);
+    });
+    world_list.add(Arc::new(GridVolume::new_emissive(
+        fire_density,
+        Point3::new(0.4, 0.0, -1.0),
+        Vec3::new(3.0, 3.0, 3.0),
+        3.0,
+        Color::new(0.3, 0.3, 0.3),
+        VolumeEmission::Temperature(temperature, 0.2),
+    )));
+
+    // A wisp of glowing smoke in twisted bands, shining where it is densest.
+    let wisp = |p: &Point3| {
+        let falloff = 1.0 - (*p - Point3::new(0.5, 0.5, 0.5)).length() / 0.45;
+        let bands = 0.5 + 0.5 * (14.0 * p.y() + 8.0 * p.x() * p.z()).sin();
+        falloff.clamp(0.0, 1.0) * bands
+    };
+    world_list.add(Arc::new(GridVolume::new_emissive(
+        VolumeGrid::from_fn([48, 48, 48], wisp),
+        Point3::new(-1.8, 0.4, 1.6),
+        Vec3::new(1.8, 1.8, 1.8),
+        4.0,
+        Color::new(0.2, 0.2, 0.2),
+        VolumeEmission::Grid(
+            VolumeGrid::from_fn([48, 48, 48], wisp),
+            Color::new(1.0, 4.0, 2.5),
+        ),
+    )));
+
+    world_list = HittableList::single(Arc::new(BvhNode::from_hittable_list(&mut world_list)));
+
+    let world = Arc::new(world_list);
+
+    let camera_settings = camera::CameraSettings {
+        num_threads: 4,
+        aspect_ratio: 16.0 / 9.0,
+        image_width: 400,
+        samples_per_pixel: 100,
+        max_depth: 50,
+        vfov: 40.0,
+        lookfrom: Point3::new(0.0, 3.0, 9.0),
+        lookat: Point3::new(0.0, 1.5, 0.0),
+        vup: Vec3::new(0.0, 1.0, 0.0),
+        defocus_angle: 0.0,
+        focus_dist: 10.0,
+        background: camera::Background::sky(),
+    };
+
+    let cam = camera::Camera::initialize(camera_settings);
+    cam.render(world);
+}
+
+fn cumulus() -> VolumeGrid {
+    // A heap of soft puffs, cut flat along the bottom.
+    let mut puffs = Vec::new();
+    for _ in 0..40 {
+        let center = Point3::new(
+            util::bounded_random_double(0.25, 0.75),
+            util::bounded_random_double(0.25, 0.55),
+            util::bounded_random_double(0.3, 0.7),
+        );
+        puffs.push((center, util::bounded_random_double(0.08, 0.16)));
+    }
+    VolumeGrid::from_fn([64, 64, 64], |p| {
+        let mut density: f64 = 0.0;
+        for (center, radius) in puffs.iter() {
+            let d2 = (*p - *center).length_squared() / (radius * radius);
+            density += (-d2).exp();
+        }
+        let base = ((p.y() - 0.2) / 0.08).clamp(0.0, 1.0);
+        (density - 0.4).clamp(0.0, 1.0) * base
+    })
+}
@@ -0,0 +1,81 @@
+use std::sync::Arc;
+
+use crate::camera;
+use crate::color::Color;
+use crate::hittables::bvh::BvhNode;
+use crate::hittables::hittable_list::HittableList;
+use crate::hittables::voxel_grid::VoxelGrid;
+use crate::material::material::{Dielectric, Lambertian, Material, Metal};
+use crate::math::vec3::{Point3, Vec3};
+
+#[allow(dead_code)]
+pub fn render() {
+    // World
+    let mut world_list = HittableList::new();
+
+    let materials: Vec<Arc<dyn Material>> = vec![
+        Arc::new(Lambertian::new(Color::new(0.3, 0.55, 0.2))), // 1: grass
+        Arc::new(Lambertian::new(Color::new(0.45, 0.3, 0.2))), // 2: dirt
+        Arc::new(Lambertian::new(Color::new(0.5, 0.5, 0.5))),  // 3: stone
+        Arc::new(Dielectric::new(1.5)),                        // 4: glass
+        Arc::new(Metal::new(Color::new(0.8, 0.6, 0.2), 0.1)),  // 5: gold
+    ];
+
+    // Blocky hills: stone at the bottom, then dirt, capped with grass.
+    let (nx, ny, nz) = (48, 16, 48);
+    let mut grid = VoxelGrid::new([nx, ny, nz], Point3::new(-12.0, 0.0, -12.0), 0.5, materials);
+    for z in 0..nz {
+        for x in 0..nx {
+            let fx = x as f64 / nx as f64;
+            let fz = z as f64 / nz as f64;
+            let height = (4.0 + 3.0 * (7.0 * fx).sin() * (5.0 * fz).cos()) as usize;
+            for y in 0..height {
+                let index = if y + 1 == height {
+                    1
+                } else if y + 3 >= height {
+                    2
+                } else {
+                    3
+                };
+                grid.set(x, y, z, index);
+            }
+        }
+    }
+
+    // A glass cube and a gold pillar sitting on the hills.
+    for z in 20..26 {
+        for x in 20..26 {
+            for y in 8..14 {
+                grid.set(x, y, z, 4);
+            }
+        }
+    }
+    for y in 0..ny {
+        grid.set(32, y, 16, 5);
+        grid.set(33, y, 16, 5);
+    }
+
+    world_list.add(Arc::new(grid));
+
+    world_list = HittableList::single(Arc::new(BvhNode::from_hittable_list(&mut world_list)));
+
+    let world = Arc::new(world_list);
+
+    let camera_settings = camera::CameraSettings {
+        num_threads: 4,
+        aspect_ratio: 16.0 / 9.0,
+        image_width: 400,
+        samples_per_pixel: 100,
+        max_depth: 50,
+        vfov: 40.0,
+        lookfrom: Point3::new(16.0, 14.0, 16.0),
+        lookat: Point3::new(0.0, 2.0, 0.0),
+        vup: Vec3::new(0.0, 1.0, 0.0),
+        defocus_angle: 0.0,
+        focus_dist: 10.0,
+        background: camera::Background::sky(),
+    };
+
+    let cam = camera::Camera::initialize(camera_settings);
+    cam.render(world);
+}
@@ -0,0 +1,174 @@
+use std::io;
+use std::sync::Arc;
+
+use crate::color::Color;
+use crate::math::transform::Transform;
+use crate::math::vec3::Vec3;
+use crate::texture::image::{ColorSpace, Image};
+use crate::util;
+
+// A piecewise-constant distribution over n equal bins of [0, 1).
+struct Distribution1D {
+    func: Vec<f64>,
+    cdf: Vec<f64>,
+    integral: f64,
+}
+
+impl Distribution1D {
+    fn new(func: Vec<f64>) -> Distribution1D {
+        let n = func.len();
+        let mut cdf = vec![0.0; n + 1];
+        for i in 0..n {
+            cdf[i + 1] = cdf[i] + func[i] / n as f64;
+        }
+        let integral = cdf[n];
+
+        // An all-black row falls back to picking uniformly.
+        for (i, c) in cdf.iter_mut().enumerate() {
+            *c = if integral > 0.0 {
+                *c / integral
+            } else {
+                i as f64 / n as f64
+            };
+        }
+        Distribution1D {
+            func,
+            cdf,
+            integral,
+        }
+    }
+
+    fn sample(&self, xi: f64) -> (f64, usize) {
+        // Returns a point in [0, 1) drawn in proportion to the function, and its bin.
+        let n = self.func.len();
+        let bin = self.cdf.partition_point(|&c| c <= xi).clamp(1, n) - 1;
+        let width = self.cdf[bin + 1] - self.cdf[bin];
+        let offset = if width > 0.0 {
+            (xi - self.cdf[bin]) / width
+        } else {
+            0.5
+        };
+        return ((bin as f64 + offset) / n as f64, bin);
+    }
+
+    fn pdf(&self, bin: usize) -> f64 {
+        // The density of sample() at any point in the bin.
+        if self.integral > 0.0 {
+            self.func[bin] / self.integral
+        } else {
+            1.0
+        }
+    }
+}
+
+// Light arriving from every direction, read from an equirectangular (latitude-longitude)
+// image. The top row looks straight up and the image wraps around the vertical axis.
+pub struct EnvironmentMap {
+    image: Arc<Image>,
+    intensity: f64,
+    map_to_world: Transform,
+    world_to_map: Transform,
+    rows: Distribution1D,
+    columns: Vec<Distribution1D>,
+}
+
+impl EnvironmentMap {
+    pub fn new(image: Arc<Image>, rotation: f64, intensity: f64) -> EnvironmentMap {
+        // Turns the map by `rotation` degrees about the vertical axis and scales its light by
+        // `intensity`. Directions are importance sampled by the brightness of each pixel,
+        // weighted by the solid angle it covers, so a small bright sun is found by most
+        // samples rather than by the odd lucky bounce.
+        let (width, height) = (image.width(), image.height());
+        let mut columns = Vec::with_capacity(height);
+        for y in 0..height {
+            let sin_theta = (util::PI * (y as f64 + 0.5) / height as f64).sin();
+            let row = (0..width)
+                .map(|x| {
+                    let c = image.pixel_data(x, y);
+                    (0.2126 * c.x() + 0.7152 * c.y() + 0.0722 * c.z()) * sin_theta
+                })
+                .collect();
+            columns.push(Distribution1D::new(row));
+        }
+        let rows = Distribution1D::new(columns.iter().map(|row| row.integral).collect());
+
+        let map_to_world = Transform::rotate(Vec3::new(0.0, 1.0, 0.0), rotation);
+        EnvironmentMap {
+            image,
+            intensity,
+            world_to_map: map_to_world.inverse(),
+            map_to_world,
+            rows,
+            columns,
+        }
+    }
+
+    pub fn load(filename: &str, rotation: f64, intensity: f64) -> io::Result<EnvironmentMap> {
+        let image = Image::load(filename, ColorSpace::Srgb)?;
+        return Ok(EnvironmentMap::new(image, rotation, intensity));
+    }
+
+    pub fn value(&self, direction: &Vec3) -> Color {
+        // The light arriving from `direction`, filtered between the four nearest pixels.
+        let (u, v) = self.direction_to_uv(direction);
+        let (width, height) = (self.image.width(), self.image.height());
+        let x = u * width as f64 - 0.5;
+        let y = v * height as f64 - 0.5;
+        let (x0, y0) = (x.floor(), y.floor());
+        let (fx, fy) = (x - x0, y - y0);
+
+        // Wrap around in longitude and stop at the poles.
+        let column = |i: f64| (i as i64).rem_euclid(width as i64) as usize;
+        let row = |j: f64| (j.max(0.0) as usize).min(height - 1);
+        let (i0, i1) = (column(x0), column(x0 + 1.0));
+        let (j0, j1) = (row(y0), row(y0 + 1.0));
+
+        let top = (1.0 - fx) * self.image.pixel_data(i0, j0) + fx * self.image.pixel_data(i1, j0);
+        let bottom =
+            (1.0 - fx) * self.image.pixel_data(i0, j1) + fx * self.image.pixel_data(i1, j1);
+        return self.intensity * ((1.0 - fy) * top + fy * bottom);
+    }
+
+    pub fn sample_direction(&self) -> Vec3 {
+        // A random direction, drawn more often where the map is bright.
+        let (v, y) = self.rows.sample(util::random_double());
+        let (u, _) = self.columns[y].sample(util::random_double());
+        return self.uv_to_direction(u, v);
+    }
+
+    pub fn pdf(&self, direction: &Vec3) -> f64 {
+        // The solid angle density of sample_direction() in `direction`.
+        let (u, v) = self.direction_to_uv(direction);
+        let sin_theta = (util::PI * v).sin();
+        if sin_theta <= 0.0 {
+            return 0.0;
+        }
+
+        let (width, height) = (self.image.width(), self.image.height());
+        let x = ((u * width as f64) as usize).min(width - 1);
+        let y = ((v * height as f64) as usize).min(height - 1);
+        let pdf_uv = self.rows.pdf(y) * self.columns[y].pdf(x);
+
+        // The map stretches u over 2 pi of longitude and v over pi of latitude, and each
+        // pixel covers sin theta of its flat area on the sphere.
+        return pdf_uv / (2.0 * util::PI * util::PI * sin_theta);
+    }
+
+    fn direction_to_uv(&self, direction: &Vec3) -> (f64, f64) {
+        // u runs around from -x, as on a textured sphere, and v runs down from straight up.
+        let d = Vec3::unit_vector(self.world_to_map.vector(direction));
+        let theta = d.y().clamp(-1.0, 1.0).acos();
+        let phi = (-d.z()).atan2(d.x()) + util::PI;
+        return (phi / (2.0 * util::PI), theta / util::PI);
+    }
+
+    fn uv_to_direction(&self, u: f64, v: f64) -> Vec3 {
+        let (theta, phi) = (util::PI * v, 2.0 * util::PI * u);
+        let d = Vec3::new(
+            -theta.sin() * phi.cos(),
+            theta.cos(),
+            theta.sin() * phi.sin(),
+        );
+        return self.map_to_world.vector(&d);
+    }
+}
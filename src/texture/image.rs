@@ -0,0 +1,146 @@
+use std::collections::HashMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+
+use crate::color::Color;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ColorSpace {
+    Srgb,   // 8 and 16-bit color maps, stored gamma encoded
+    Linear, // Data such as heights or roughness, and anything stored as floats
+}
+
+type ImageCache = Mutex<HashMap<(PathBuf, ColorSpace), Arc<Image>>>;
+
+// An image held as linear colors, row by row from the top.
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Color>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, pixels: Vec<Color>) -> Image {
+        assert_eq!(
+            pixels.len(),
+            width * height,
+            "image needs one color per pixel"
+        );
+        Image {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    pub fn load(filename: &str, color_space: ColorSpace) -> io::Result<Arc<Image>> {
+        // Looks for the file as given, then in an `images` directory in the working
+        // directory or any of its parents. PNG, JPEG, Radiance HDR and PNM files are read.
+        // Each file is only decoded once, and later loads share the same image.
+        static CACHE: OnceLock<ImageCache> = OnceLock::new();
+
+        let path = Image::find(filename).ok_or_else(|| {
+            io::Error::new(io::ErrorKind::NotFound, "not found in any images directory")
+        })?;
+        let key = (path.canonicalize()?, color_space);
+
+        let cache = CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+        if let Some(image) = cache.lock().unwrap().get(&key) {
+            return Ok(image.clone());
+        }
+
+        let image = Arc::new(Image::decode(&key.0, color_space)?);
+        cache.lock().unwrap().insert(key, image.clone());
+        return Ok(image);
+    }
+
+    fn decode(path: &Path, color_space: ColorSpace) -> io::Result<Image> {
+        let decoded = image::open(path)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.to_string()))?;
+
+        // Float formats such as HDR already hold linear values.
+        let is_float = matches!(
+            decoded.color(),
+            image::ColorType::Rgb32F | image::ColorType::Rgba32F
+        );
+        let decode_srgb = color_space == ColorSpace::Srgb && !is_float;
+
+        let rgb = decoded.into_rgb32f();
+        let channel = |c: f32| {
+            let c = c as f64;
+            if decode_srgb {
+                srgb_to_linear(c)
+            } else {
+                c
+            }
+        };
+        let pixels = rgb
+            .pixels()
+            .map(|p| Color::new(channel(p[0]), channel(p[1]), channel(p[2])))
+            .collect();
+        return Ok(Image::new(
+            rgb.width() as usize,
+            rgb.height() as usize,
+            pixels,
+        ));
+    }
+
+    fn find(filename: &str) -> Option<PathBuf> {
+        let path = Path::new(filename);
+        if path.is_file() {
+            return Some(path.to_path_buf());
+        }
+
+        let mut dir = std::env::current_dir().ok();
+        while let Some(current) = dir {
+            let candidate = current.join("images").join(filename);
+            if candidate.is_file() {
+                return Some(candidate);
+            }
+            dir = current.parent().map(Path::to_path_buf);
+        }
+        return None;
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn pixel_data(&self, x: usize, y: usize) -> Color {
+        // Returns the color of pixel x, y, clamping coordinates that fall off the image.
+        let x = x.min(self.width - 1);
+        let y = y.min(self.height - 1);
+        return self.pixels[y * self.width + x];
+    }
+
+    pub fn downsample(&self) -> Image {
+        // Halves the image with a 2x2 box filter, rounding odd sizes up. The missing row or
+        // column past an odd edge repeats the last one.
+        let width = self.width.div_ceil(2).max(1);
+        let height = self.height.div_ceil(2).max(1);
+        let mut pixels = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                let sum = self.pixel_data(2 * x, 2 * y)
+                    + self.pixel_data(2 * x + 1, 2 * y)
+                    + self.pixel_data(2 * x, 2 * y + 1)
+                    + self.pixel_data(2 * x + 1, 2 * y + 1);
+                pixels.push(sum / 4.0);
+            }
+        }
+        return Image::new(width, height, pixels);
+    }
+}
+
+fn srgb_to_linear(c: f64) -> f64 {
+    // The sRGB transfer curve: linear near black, a 2.4 power above.
+    if c <= 0.04045 {
+        return c / 12.92;
+    }
+    return ((c + 0.055) / 1.055).powf(2.4);
+}
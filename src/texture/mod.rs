@@ -0,0 +1,6 @@
+pub mod environment;
+pub mod image;
+pub mod noise;
+pub mod sky;
+#[allow(clippy::module_inception)]
+pub mod texture;
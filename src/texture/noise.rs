@@ -0,0 +1,180 @@
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+use crate::math::vec3::{Point3, Vec3};
+
+const POINT_COUNT: usize = 256;
+
+// Ken Perlin's improved noise, with the permutation table shuffled from a seed so that the
+// same seed always gives the same pattern.
+pub struct Perlin {
+    perm: [usize; 2 * POINT_COUNT],
+}
+
+impl Perlin {
+    pub fn new(seed: u64) -> Perlin {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut table: Vec<usize> = (0..POINT_COUNT).collect();
+        table.shuffle(&mut rng);
+
+        // Doubled up so lookups of a cell's far corners never need wrapping.
+        let mut perm = [0; 2 * POINT_COUNT];
+        for i in 0..2 * POINT_COUNT {
+            perm[i] = table[i % POINT_COUNT];
+        }
+        Perlin { perm }
+    }
+
+    pub fn noise(&self, p: &Point3) -> f64 {
+        // Smoothly varying noise in roughly [-1, 1], zero at every lattice point.
+        let xi = p.x().floor();
+        let yi = p.y().floor();
+        let zi = p.z().floor();
+        let (x, y, z) = (p.x() - xi, p.y() - yi, p.z() - zi);
+        let i = (xi as i64).rem_euclid(POINT_COUNT as i64) as usize;
+        let j = (yi as i64).rem_euclid(POINT_COUNT as i64) as usize;
+        let k = (zi as i64).rem_euclid(POINT_COUNT as i64) as usize;
+
+        let (u, v, w) = (fade(x), fade(y), fade(z));
+
+        let a = self.perm[i] + j;
+        let aa = self.perm[a] + k;
+        let ab = self.perm[a + 1] + k;
+        let b = self.perm[i + 1] + j;
+        let ba = self.perm[b] + k;
+        let bb = self.perm[b + 1] + k;
+
+        let g = |hash: usize, dx: f64, dy: f64, dz: f64| grad(self.perm[hash], dx, dy, dz);
+        return lerp(
+            w,
+            lerp(
+                v,
+                lerp(u, g(aa, x, y, z), g(ba, x - 1.0, y, z)),
+                lerp(u, g(ab, x, y - 1.0, z), g(bb, x - 1.0, y - 1.0, z)),
+            ),
+            lerp(
+                v,
+                lerp(u, g(aa + 1, x, y, z - 1.0), g(ba + 1, x - 1.0, y, z - 1.0)),
+                lerp(
+                    u,
+                    g(ab + 1, x, y - 1.0, z - 1.0),
+                    g(bb + 1, x - 1.0, y - 1.0, z - 1.0),
+                ),
+            ),
+        );
+    }
+
+    pub fn turb(&self, p: &Point3, depth: usize) -> f64 {
+        // Sum of the magnitudes of ever finer and fainter octaves, for a billowy look.
+        let mut accum = 0.0;
+        let mut temp_p = *p;
+        let mut weight = 1.0;
+
+        for _ in 0..depth {
+            accum += weight * self.noise(&temp_p).abs();
+            weight *= 0.5;
+            temp_p *= 2.0;
+        }
+
+        return accum;
+    }
+
+    pub fn fbm(&self, p: &Point3, octaves: usize, lacunarity: f64, gain: f64) -> f64 {
+        // Fractional Brownian motion: signed octaves, each `lacunarity` times finer and
+        // `gain` times fainter than the last.
+        let mut accum = 0.0;
+        let mut temp_p = *p;
+        let mut weight = 1.0;
+
+        for _ in 0..octaves {
+            accum += weight * self.noise(&temp_p);
+            weight *= gain;
+            temp_p *= lacunarity;
+        }
+
+        return accum;
+    }
+}
+
+fn fade(t: f64) -> f64 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+fn lerp(t: f64, a: f64, b: f64) -> f64 {
+    a + t * (b - a)
+}
+
+fn grad(hash: usize, x: f64, y: f64, z: f64) -> f64 {
+    // Dot product with one of the twelve directions to the edges of a cube.
+    let h = hash & 15;
+    let u = if h < 8 { x } else { y };
+    let v = if h < 4 {
+        y
+    } else if h == 12 || h == 14 {
+        x
+    } else {
+        z
+    };
+    return (if h & 1 == 0 { u } else { -u }) + (if h & 2 == 0 { v } else { -v });
+}
+
+// Steven Worley's cellular noise. Every unit cell holds one feature point at a seeded,
+// random spot, and the noise is the distance to the nearest ones.
+pub struct Worley {
+    perm: [usize; POINT_COUNT],
+    offsets: [Vec3; POINT_COUNT],
+}
+
+impl Worley {
+    pub fn new(seed: u64) -> Worley {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut table: Vec<usize> = (0..POINT_COUNT).collect();
+        table.shuffle(&mut rng);
+
+        let mut perm = [0; POINT_COUNT];
+        perm.copy_from_slice(&table);
+        let offsets = [(); POINT_COUNT].map(|_| {
+            Vec3::new(
+                rng.random::<f64>(),
+                rng.random::<f64>(),
+                rng.random::<f64>(),
+            )
+        });
+        Worley { perm, offsets }
+    }
+
+    fn feature_point(&self, i: i64, j: i64, k: i64) -> Point3 {
+        let wrap = |n: i64| n.rem_euclid(POINT_COUNT as i64) as usize;
+        let hash = self.perm
+            [(self.perm[(self.perm[wrap(i)] + wrap(j)) % POINT_COUNT] + wrap(k)) % POINT_COUNT];
+        return Point3::new(i as f64, j as f64, k as f64) + self.offsets[hash];
+    }
+
+    pub fn distances(&self, p: &Point3) -> (f64, f64) {
+        // Returns the distances to the nearest and second nearest feature points, searching
+        // the 3x3x3 block of cells around p, which holds them in all but freak layouts.
+        let (ci, cj, ck) = (
+            p.x().floor() as i64,
+            p.y().floor() as i64,
+            p.z().floor() as i64,
+        );
+
+        let mut f1 = f64::INFINITY;
+        let mut f2 = f64::INFINITY;
+        for i in ci - 1..=ci + 1 {
+            for j in cj - 1..=cj + 1 {
+                for k in ck - 1..=ck + 1 {
+                    let d = (self.feature_point(i, j, k) - *p).length();
+                    if d < f1 {
+                        f2 = f1;
+                        f1 = d;
+                    } else if d < f2 {
+                        f2 = d;
+                    }
+                }
+            }
+        }
+        return (f1, f2);
+    }
+}
@@ -0,0 +1,175 @@
+use crate::color::Color;
+use crate::math::onb::Onb;
+use crate::math::vec3::Vec3;
+use crate::util;
+
+// The sun's angular radius, in degrees.
+const SUN_RADIUS: f64 = 0.2665;
+
+// The sun's luminance above the atmosphere, in thousands of candela per square meter. This
+// gives about 100000 lux from a sun overhead before the air dims it.
+const SUN_LUMINANCE: f64 = 1.5e6;
+
+// A clear daylight sky after Preetham, Shirley and Smits, "A Practical Analytic Model for
+// Daylight", with the sun as a small disk. Both fall off towards the horizon and redden as
+// the sun sets. Turbidity is the haziness of the air, from about 2 for a crisp blue sky to
+// 10 for a hazy one. Luminance is in thousands of candela per square meter before scaling
+// by `intensity`, so scenes lit by a high sun want around 0.05 to 0.1.
+pub struct PhysicalSky {
+    sun_direction: Vec3,
+    sun_frame: Onb,
+    sun_radiance: Color,
+    cos_sun_radius: f64,
+    zenith: [f64; 3], // Luminance Y, and chromaticity x and y, straight up
+    coefficients: [[f64; 5]; 3], // The distribution's A to E for each of Y, x and y
+    theta_s: f64,
+    intensity: f64,
+}
+
+impl PhysicalSky {
+    pub fn new(elevation: f64, azimuth: f64, turbidity: f64, intensity: f64) -> PhysicalSky {
+        // The sun sits `elevation` degrees above the horizon, and `azimuth` degrees around
+        // from -z towards +x. The sky model only covers a sun above the horizon, so a lower
+        // one gives the sky at sunset and no sun disk.
+        let (el, az) = (
+            util::degrees_to_radians(elevation),
+            util::degrees_to_radians(azimuth),
+        );
+        let sun_direction = Vec3::new(el.cos() * az.sin(), el.sin(), -el.cos() * az.cos());
+        let theta_s = util::PI / 2.0 - el.max(0.0);
+        let t = turbidity;
+
+        // Zenith luminance and chromaticity.
+        let chi = (4.0 / 9.0 - t / 120.0) * (util::PI - 2.0 * theta_s);
+        let zenith_y = (4.0453 * t - 4.9710) * chi.tan() - 0.2155 * t + 2.4192;
+        let chromaticity = |m: [[f64; 4]; 3]| {
+            let th = [theta_s.powi(3), theta_s.powi(2), theta_s, 1.0];
+            let row = |r: [f64; 4]| r.iter().zip(th).map(|(a, b)| a * b).sum::<f64>();
+            t * t * row(m[0]) + t * row(m[1]) + row(m[2])
+        };
+        let zenith_x = chromaticity([
+            [0.00166, -0.00375, 0.00209, 0.0],
+            [-0.02903, 0.06377, -0.03202, 0.00394],
+            [0.11693, -0.21196, 0.06052, 0.25886],
+        ]);
+        let zenith_chroma_y = chromaticity([
+            [0.00275, -0.00610, 0.00317, 0.0],
+            [-0.04214, 0.08970, -0.04153, 0.00516],
+            [0.15346, -0.26756, 0.06670, 0.26688],
+        ]);
+
+        let coefficients = [
+            [
+                0.1787 * t - 1.4630,
+                -0.3554 * t + 0.4275,
+                -0.0227 * t + 5.3251,
+                0.1206 * t - 2.5771,
+                -0.0670 * t + 0.3703,
+            ],
+            [
+                -0.0193 * t - 0.2592,
+                -0.0665 * t + 0.0008,
+                -0.0004 * t + 0.2125,
+                -0.0641 * t - 0.8989,
+                -0.0033 * t + 0.0452,
+            ],
+            [
+                -0.0167 * t - 0.2608,
+                -0.0950 * t + 0.0092,
+                -0.0079 * t + 0.2102,
+                -0.0441 * t - 1.6537,
+                -0.0109 * t + 0.0529,
+            ],
+        ];
+
+        let sun_radiance = if elevation > 0.0 {
+            SUN_LUMINANCE * sun_transmittance(theta_s, t)
+        } else {
+            Color::blank()
+        };
+
+        PhysicalSky {
+            sun_direction,
+            sun_frame: Onb::new(&sun_direction),
+            sun_radiance,
+            cos_sun_radius: util::degrees_to_radians(SUN_RADIUS).cos(),
+            zenith: [zenith_y.max(0.0), zenith_x, zenith_chroma_y],
+            coefficients,
+            theta_s,
+            intensity,
+        }
+    }
+
+    pub fn value(&self, direction: &Vec3) -> Color {
+        // The light arriving from `direction`. Below the horizon the sky keeps its color
+        // at the horizon.
+        let d = Vec3::unit_vector(*direction);
+        let cos_gamma = Vec3::dot(&d, &self.sun_direction);
+        if cos_gamma >= self.cos_sun_radius {
+            return self.intensity * self.sun_radiance;
+        }
+
+        let theta = d.y().clamp(0.001, 1.0).acos();
+        let gamma = cos_gamma.clamp(-1.0, 1.0).acos();
+        let [y, x, cy] = [0, 1, 2].map(|i| {
+            self.zenith[i] * self.perez(i, theta, gamma) / self.perez(i, 0.0, self.theta_s)
+        });
+        return self.intensity * xyy_to_rgb(x, cy, y);
+    }
+
+    pub fn sample_direction(&self) -> Vec3 {
+        // A random direction within the sun's disk.
+        let cos_theta = 1.0 + util::random_double() * (self.cos_sun_radius - 1.0);
+        let sin_theta = (1.0 - cos_theta * cos_theta).sqrt();
+        let phi = 2.0 * util::PI * util::random_double();
+        let local = Vec3::new(sin_theta * phi.cos(), sin_theta * phi.sin(), cos_theta);
+        return self.sun_frame.transform(&local);
+    }
+
+    pub fn pdf(&self, direction: &Vec3) -> f64 {
+        // The solid angle density of sample_direction(), even across the sun's disk.
+        let cos_gamma = Vec3::dot(&Vec3::unit_vector(*direction), &self.sun_direction);
+        if cos_gamma < self.cos_sun_radius {
+            return 0.0;
+        }
+        return 1.0 / (2.0 * util::PI * (1.0 - self.cos_sun_radius));
+    }
+
+    fn perez(&self, i: usize, theta: f64, gamma: f64) -> f64 {
+        // The Perez sky distribution for view angle theta from the zenith and gamma from
+        // the sun.
+        let [a, b, c, d, e] = self.coefficients[i];
+        return (1.0 + a * (b / theta.cos()).exp())
+            * (1.0 + c * (d * gamma).exp() + e * gamma.cos().powi(2));
+    }
+}
+
+fn sun_transmittance(theta_s: f64, turbidity: f64) -> Color {
+    // The fraction of sunlight reaching the ground at red, green and blue wavelengths, from
+    // Rayleigh scattering by air and Angstrom's formula for haze. The air mass is Kasten's
+    // fit, which stays finite with the sun on the horizon.
+    let zenith_degrees = theta_s.to_degrees();
+    let air_mass = 1.0 / (theta_s.cos() + 0.15 * (93.885 - zenith_degrees).powf(-1.253));
+    let beta = 0.04608 * turbidity - 0.04586;
+    let channel = |lambda: f64| {
+        // Wavelength in micrometers.
+        let rayleigh = 0.008735 * lambda.powf(-4.08);
+        let haze = beta * lambda.powf(-1.3);
+        (-air_mass * (rayleigh + haze)).exp()
+    };
+    return Color::new(channel(0.65), channel(0.57), channel(0.475));
+}
+
+fn xyy_to_rgb(x: f64, y: f64, luminance: f64) -> Color {
+    // CIE xyY to linear sRGB.
+    if y <= 0.0 {
+        return Color::blank();
+    }
+    let cx = x / y * luminance;
+    let cz = (1.0 - x - y) / y * luminance;
+    return Color::new(
+        (3.2406 * cx - 1.5372 * luminance - 0.4986 * cz).max(0.0),
+        (-0.9689 * cx + 1.8758 * luminance + 0.0415 * cz).max(0.0),
+        (0.0557 * cx - 0.2040 * luminance + 1.0570 * cz).max(0.0),
+    );
+}
@@ -0,0 +1,393 @@
+use std::sync::Arc;
+
+use crate::color::Color;
+use crate::hittables::hittable::HitRecord;
+use crate::math::vec3::Point3;
+use crate::texture::image::{ColorSpace, Image};
+use crate::texture::noise::{Perlin, Worley};
+
+pub trait Texture: Send + Sync {
+    fn value(&self, u: f64, v: f64, p: &Point3) -> Color;
+
+    fn sample(&self, rec: &HitRecord) -> Color {
+        // The texture's color at a hit. Textures that can filter over the area the ray's
+        // differentials cover at the hit override this.
+        return self.value(rec.u, rec.v, &rec.p);
+    }
+}
+
+pub struct SolidColor {
+    albedo: Color,
+}
+
+impl SolidColor {
+    pub fn new(albedo: Color) -> SolidColor {
+        SolidColor { albedo }
+    }
+
+    pub fn from_value(value: f64) -> SolidColor {
+        // A gray, for textures that stand for a single number such as roughness.
+        SolidColor::new(Color::new(value, value, value))
+    }
+}
+
+impl Texture for SolidColor {
+    fn value(&self, _u: f64, _v: f64, _p: &Point3) -> Color {
+        return self.albedo;
+    }
+}
+
+// A 3D checkerboard of cubes `scale` wide, filling space so any surface cut through it is
+// checkered without needing texture coordinates.
+pub struct CheckerTexture {
+    inv_scale: f64,
+    even: Arc<dyn Texture>,
+    odd: Arc<dyn Texture>,
+}
+
+impl CheckerTexture {
+    pub fn new(scale: f64, even: Arc<dyn Texture>, odd: Arc<dyn Texture>) -> CheckerTexture {
+        CheckerTexture {
+            inv_scale: 1.0 / scale,
+            even,
+            odd,
+        }
+    }
+
+    pub fn from_colors(scale: f64, c1: Color, c2: Color) -> CheckerTexture {
+        CheckerTexture::new(
+            scale,
+            Arc::new(SolidColor::new(c1)),
+            Arc::new(SolidColor::new(c2)),
+        )
+    }
+}
+
+impl Texture for CheckerTexture {
+    fn value(&self, u: f64, v: f64, p: &Point3) -> Color {
+        let x_integer = (self.inv_scale * p.x()).floor() as i64;
+        let y_integer = (self.inv_scale * p.y()).floor() as i64;
+        let z_integer = (self.inv_scale * p.z()).floor() as i64;
+
+        let is_even = (x_integer + y_integer + z_integer) % 2 == 0;
+
+        return if is_even {
+            self.even.value(u, v, p)
+        } else {
+            self.odd.value(u, v, p)
+        };
+    }
+}
+
+// A checkerboard laid out over the texture coordinates, with `u_squares` by `v_squares`
+// squares covering the surface.
+pub struct UvCheckerTexture {
+    u_squares: f64,
+    v_squares: f64,
+    even: Arc<dyn Texture>,
+    odd: Arc<dyn Texture>,
+}
+
+impl UvCheckerTexture {
+    pub fn new(
+        u_squares: f64,
+        v_squares: f64,
+        even: Arc<dyn Texture>,
+        odd: Arc<dyn Texture>,
+    ) -> UvCheckerTexture {
+        UvCheckerTexture {
+            u_squares,
+            v_squares,
+            even,
+            odd,
+        }
+    }
+
+    pub fn from_colors(u_squares: f64, v_squares: f64, c1: Color, c2: Color) -> UvCheckerTexture {
+        UvCheckerTexture::new(
+            u_squares,
+            v_squares,
+            Arc::new(SolidColor::new(c1)),
+            Arc::new(SolidColor::new(c2)),
+        )
+    }
+}
+
+impl Texture for UvCheckerTexture {
+    fn value(&self, u: f64, v: f64, p: &Point3) -> Color {
+        let u_integer = (self.u_squares * u).floor() as i64;
+        let v_integer = (self.v_squares * v).floor() as i64;
+
+        return if (u_integer + v_integer) % 2 == 0 {
+            self.even.value(u, v, p)
+        } else {
+            self.odd.value(u, v, p)
+        };
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Filter {
+    Nearest,   // The color of the pixel the point falls in
+    Bilinear,  // A blend of the four pixel centers around the point
+    Trilinear, // Bilinear in the mip levels matching the ray's footprint
+}
+
+#[derive(Clone, Copy)]
+pub enum AddressMode {
+    Wrap,   // Tile the image
+    Clamp,  // Stretch the edge pixels out
+    Mirror, // Tile the image, flipping every other copy
+}
+
+pub struct ImageTexture {
+    // The image followed by its mip levels, each half the size of the one before. Only
+    // trilinear filtering builds the smaller levels. Empty if the image failed to load.
+    levels: Vec<Arc<Image>>,
+    filter: Filter,
+    address: AddressMode,
+}
+
+impl ImageTexture {
+    pub fn new(filename: &str) -> ImageTexture {
+        ImageTexture::with_sampling(filename, Filter::Trilinear, AddressMode::Clamp)
+    }
+
+    pub fn with_sampling(filename: &str, filter: Filter, address: AddressMode) -> ImageTexture {
+        // A missing or unreadable image is reported and rendered in solid cyan, so the scene
+        // still renders and the problem is easy to spot.
+        match Image::load(filename, ColorSpace::Srgb) {
+            Ok(image) => ImageTexture::from_image(image, filter, address),
+            Err(err) => {
+                eprintln!("ERROR: Could not load image file '{}': {}", filename, err);
+                ImageTexture {
+                    levels: Vec::new(),
+                    filter,
+                    address,
+                }
+            }
+        }
+    }
+
+    pub fn from_image(image: Arc<Image>, filter: Filter, address: AddressMode) -> ImageTexture {
+        let mut levels = vec![image];
+        if filter == Filter::Trilinear {
+            loop {
+                let last = &levels[levels.len() - 1];
+                if last.width() <= 1 && last.height() <= 1 {
+                    break;
+                }
+                let next = Arc::new(last.downsample());
+                levels.push(next);
+            }
+        }
+        ImageTexture {
+            levels,
+            filter,
+            address,
+        }
+    }
+
+    fn address(&self, i: i64, n: usize) -> usize {
+        let n = n as i64;
+        let i = match self.address {
+            AddressMode::Wrap => i.rem_euclid(n),
+            AddressMode::Clamp => i.clamp(0, n - 1),
+            AddressMode::Mirror => {
+                let m = i.rem_euclid(2 * n);
+                if m < n {
+                    m
+                } else {
+                    2 * n - 1 - m
+                }
+            }
+        };
+        return i as usize;
+    }
+
+    fn nearest(&self, image: &Image, u: f64, v: f64) -> Color {
+        // Flip V to image coordinates, which run down from the top.
+        let x = u * image.width() as f64;
+        let y = (1.0 - v) * image.height() as f64;
+
+        let i = self.address(x.floor() as i64, image.width());
+        let j = self.address(y.floor() as i64, image.height());
+        return image.pixel_data(i, j);
+    }
+
+    fn bilinear(&self, image: &Image, u: f64, v: f64) -> Color {
+        // Measure from pixel centers, which sit half a pixel in from their corners.
+        let (width, height) = (image.width(), image.height());
+        let x = u * width as f64 - 0.5;
+        let y = (1.0 - v) * height as f64 - 0.5;
+        let (x0, y0) = (x.floor(), y.floor());
+        let (fx, fy) = (x - x0, y - y0);
+        let (x0, y0) = (x0 as i64, y0 as i64);
+
+        let (i0, i1) = (self.address(x0, width), self.address(x0 + 1, width));
+        let (j0, j1) = (self.address(y0, height), self.address(y0 + 1, height));
+        let top = (1.0 - fx) * image.pixel_data(i0, j0) + fx * image.pixel_data(i1, j0);
+        let bottom = (1.0 - fx) * image.pixel_data(i0, j1) + fx * image.pixel_data(i1, j1);
+        return (1.0 - fy) * top + fy * bottom;
+    }
+
+    fn trilinear(&self, u: f64, v: f64, width: f64) -> Color {
+        // Picks the pair of levels whose pixels are closest in size to the footprint, given
+        // as a width in full-size pixels, and blends bilinear lookups in each.
+        let last = (self.levels.len() - 1) as f64;
+        let level = width.max(1e-8).log2().clamp(0.0, last);
+        let lower = level.floor();
+        let t = level - lower;
+
+        let fine = self.bilinear(&self.levels[lower as usize], u, v);
+        if t == 0.0 {
+            return fine;
+        }
+        let coarse = self.bilinear(&self.levels[lower as usize + 1], u, v);
+        return (1.0 - t) * fine + t * coarse;
+    }
+}
+
+impl Texture for ImageTexture {
+    fn value(&self, u: f64, v: f64, _p: &Point3) -> Color {
+        let image = match self.levels.first() {
+            Some(image) if image.height() > 0 => image,
+            _ => return Color::new(0.0, 1.0, 1.0),
+        };
+
+        match self.filter {
+            Filter::Nearest => self.nearest(image, u, v),
+            Filter::Bilinear | Filter::Trilinear => self.bilinear(image, u, v),
+        }
+    }
+
+    fn sample(&self, rec: &HitRecord) -> Color {
+        // With differentials, trilinear filtering sizes the lookup to the pixel's footprint
+        // in texture space. Without them, as after a diffuse bounce, it falls back to the
+        // full-size image.
+        let d = match (&rec.differentials, self.filter) {
+            (Some(d), Filter::Trilinear) if !self.levels.is_empty() => d,
+            _ => return self.value(rec.u, rec.v, &rec.p),
+        };
+
+        let (w, h) = (
+            self.levels[0].width() as f64,
+            self.levels[0].height() as f64,
+        );
+        let along_x = (d.dudx * w).hypot(d.dvdx * h);
+        let along_y = (d.dudy * w).hypot(d.dvdy * h);
+        return self.trilinear(rec.u, rec.v, along_x.max(along_y));
+    }
+}
+
+// Gray fractal noise, centered on mid-gray.
+pub struct NoiseTexture {
+    noise: Perlin,
+    scale: f64,
+}
+
+impl NoiseTexture {
+    pub fn new(seed: u64, scale: f64) -> NoiseTexture {
+        NoiseTexture {
+            noise: Perlin::new(seed),
+            scale,
+        }
+    }
+}
+
+impl Texture for NoiseTexture {
+    fn value(&self, _u: f64, _v: f64, p: &Point3) -> Color {
+        let n = self.noise.fbm(&(self.scale * *p), 6, 2.0, 0.5);
+        return 0.5 * (1.0 + n) * Color::new(1.0, 1.0, 1.0);
+    }
+}
+
+// Bands along z, their frequency set by `scale`, bent into veins by turbulence.
+pub struct MarbleTexture {
+    noise: Perlin,
+    scale: f64,
+    base: Color,
+    vein: Color,
+}
+
+impl MarbleTexture {
+    pub fn new(seed: u64, scale: f64, base: Color, vein: Color) -> MarbleTexture {
+        MarbleTexture {
+            noise: Perlin::new(seed),
+            scale,
+            base,
+            vein,
+        }
+    }
+}
+
+impl Texture for MarbleTexture {
+    fn value(&self, _u: f64, _v: f64, p: &Point3) -> Color {
+        let t = 0.5 * (1.0 + (self.scale * p.z() + 10.0 * self.noise.turb(p, 7)).sin());
+        return t * self.base + (1.0 - t) * self.vein;
+    }
+}
+
+// Growth rings around the y axis, `scale` rings per unit, wobbled by noise.
+pub struct WoodTexture {
+    noise: Perlin,
+    scale: f64,
+    light: Color,
+    dark: Color,
+}
+
+impl WoodTexture {
+    pub fn new(seed: u64, scale: f64, light: Color, dark: Color) -> WoodTexture {
+        WoodTexture {
+            noise: Perlin::new(seed),
+            scale,
+            light,
+            dark,
+        }
+    }
+}
+
+impl Texture for WoodTexture {
+    fn value(&self, _u: f64, _v: f64, p: &Point3) -> Color {
+        let q = self.scale * *p;
+        let radius = (q.x() * q.x() + q.z() * q.z()).sqrt();
+        let grain = 0.4
+            * self
+                .noise
+                .fbm(&Point3::new(q.x(), 0.1 * q.y(), q.z()), 4, 2.0, 0.5);
+        let ring = (radius + grain).rem_euclid(1.0);
+
+        // Each ring fades slowly from light earlywood to a sharp band of dark latewood.
+        let t = ring.powi(4);
+        return (1.0 - t) * self.light + t * self.dark;
+    }
+}
+
+// Cells like cobbles or scales, with `edge` along the borders between cells fading into
+// `cell` towards their middles.
+pub struct WorleyTexture {
+    noise: Worley,
+    scale: f64,
+    cell: Color,
+    edge: Color,
+}
+
+impl WorleyTexture {
+    pub fn new(seed: u64, scale: f64, cell: Color, edge: Color) -> WorleyTexture {
+        WorleyTexture {
+            noise: Worley::new(seed),
+            scale,
+            cell,
+            edge,
+        }
+    }
+}
+
+impl Texture for WorleyTexture {
+    fn value(&self, _u: f64, _v: f64, p: &Point3) -> Color {
+        // F2 - F1 is zero on the borders, where two feature points are equally near.
+        let (f1, f2) = self.noise.distances(&(self.scale * *p));
+        let t = (4.0 * (f2 - f1)).min(1.0);
+        return t * self.cell + (1.0 - t) * self.edge;
+    }
+}
@@ -3,7 +3,7 @@
 use rand::{rngs::ThreadRng, Rng};
 
 pub const INFINITY: f64 = f64::INFINITY;
-pub const PI: f64 = 3.1415926535897932385;
+pub const PI: f64 = std::f64::consts::PI;
 
 // Utility Functions
 